@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use clap::Args;
+
+/// Where a day's puzzle input comes from.
+#[derive(Args)]
+pub struct InputArgs {
+    /// Use the day's sample.txt instead of problem.txt (or problem.txt.enc)
+    #[arg(long)]
+    sample: bool,
+    /// Read the input from this file instead
+    #[arg(long, conflicts_with = "sample")]
+    input: Option<PathBuf>,
+}
+impl InputArgs {
+    pub fn read(&self, day: u8) -> Result<String> {
+        let path = match &self.input {
+            Some(path) => path.clone(),
+            None => day_file(day, if self.sample { "sample.txt" } else { "problem.txt" }),
+        };
+        puzzle_input::read(path)
+    }
+}
+
+/// Encrypts each day's problem.txt in place, or every day's when none are given.
+pub fn encrypt(days: &[u8]) -> Result<()> {
+    let passphrase = puzzle_input::passphrase()?;
+    for path in problem_files(days, |path| path.exists())? {
+        let encrypted = puzzle_input::encrypt_file(&path, &passphrase)?;
+        println!("Encrypted {}", encrypted.display());
+    }
+    Ok(())
+}
+
+/// Decrypts each day's problem.txt.enc in place, or every day's when none are given.
+pub fn decrypt(days: &[u8]) -> Result<()> {
+    let passphrase = puzzle_input::passphrase()?;
+    for path in problem_files(days, |path| puzzle_input::encrypted_path(path).exists())? {
+        puzzle_input::decrypt_file(&path, &passphrase)?;
+        println!("Decrypted {}", path.display());
+    }
+    Ok(())
+}
+
+fn problem_files(days: &[u8], present: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
+    if days.is_empty() {
+        return Ok((1..=25)
+            .map(|day| day_file(day, "problem.txt"))
+            .filter(|path| present(path))
+            .collect());
+    }
+    days.iter()
+        .map(|&day| {
+            let path = day_file(day, "problem.txt");
+            if !present(&path) {
+                anyhow::bail!("Day {day} has nothing to convert");
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// A file in the `dayN` crate directory of this workspace.
+pub fn day_file(day: u8, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{day}"))
+        .join(name)
+}
@@ -0,0 +1,307 @@
+//! `aoc log` and `aoc stats`: a personal record of how long each part took.
+//!
+//! Events are appended as JSON lines to `.aoc-log.jsonl` at the workspace
+//! root (ignored by git), or to the file named by `AOC_LOG`.  Each event notes
+//! the commit that was checked out when it happened.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use crate::input::day_file;
+
+#[derive(Subcommand)]
+pub enum Action {
+    /// Start the clock on a part
+    Start { day: u8, part: u8 },
+    /// Stop the clock, timing the solution on problem.txt
+    Stop { day: u8, part: u8 },
+    /// Count a wrong submission
+    Wrong {
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Start,
+    Stop,
+    Wrong,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Event {
+    kind: Kind,
+    day: u8,
+    part: u8,
+    /// Seconds since the Unix epoch
+    time: u64,
+    commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    runtime_ms: Option<f64>,
+}
+
+pub fn run(action: Action) -> Result<()> {
+    let path = log_file();
+    let events = read(&path)?;
+    let (kind, day, part, answer) = match action {
+        Action::Start { day, part } => (Kind::Start, day, part, None),
+        Action::Stop { day, part } => (Kind::Stop, day, part, None),
+        Action::Wrong { day, part, answer } => (Kind::Wrong, day, part, answer),
+    };
+    let solution = solutions::find(day)?;
+    solution.part(part)?;
+
+    let running = is_running(&events, day, part);
+    match kind {
+        Kind::Start if running => anyhow::bail!("Day {day} part {part} is already started"),
+        Kind::Stop if !running => anyhow::bail!("Day {day} part {part} was not started"),
+        _ => {}
+    }
+
+    let mut event = Event {
+        kind,
+        day,
+        part,
+        time: now(),
+        commit: commit(),
+        answer,
+        runtime_ms: None,
+    };
+    if kind == Kind::Stop {
+        // Time the solution as it stands, but never lose the stop to a bad run
+        match time_solution(solution, part) {
+            Ok((answer, runtime_ms)) => {
+                println!("Day {day} part {part} = {answer} in {runtime_ms:.3} ms");
+                event.answer = Some(answer);
+                event.runtime_ms = Some(runtime_ms);
+            }
+            Err(e) => eprintln!("Could not time the solution: {e:#}"),
+        }
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Could not open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&event)?)?;
+    Ok(())
+}
+
+pub fn stats() -> Result<()> {
+    let events = read(&log_file())?;
+    if events.is_empty() {
+        println!("Nothing logged yet, try `aoc log start 1 1`");
+        return Ok(());
+    }
+    println!(
+        "{:>3} {:>4} {:>10} {:>5} {:>12}  commit",
+        "day", "part", "time", "wrong", "runtime"
+    );
+    for s in summarize(&events, now()) {
+        let time = format!(
+            "{}{}",
+            duration(s.seconds),
+            if s.running { "+" } else { "" }
+        );
+        let runtime = s
+            .runtime_ms
+            .map(|ms| format!("{ms:.3} ms"))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:>3} {:>4} {:>10} {:>5} {:>12}  {}",
+            s.day,
+            s.part,
+            time,
+            s.wrong,
+            runtime,
+            s.commit.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+/// Totals for one part across all of its sessions.
+#[derive(Debug, PartialEq)]
+struct PartStats {
+    day: u8,
+    part: u8,
+    /// Time spent between starts and stops, including an unfinished session
+    seconds: u64,
+    running: bool,
+    wrong: usize,
+    /// Runtime of the solution at the last stop
+    runtime_ms: Option<f64>,
+    /// Commit of the last stop
+    commit: Option<String>,
+}
+
+fn summarize(events: &[Event], now: u64) -> Vec<PartStats> {
+    let mut parts = BTreeMap::new();
+    let mut started = BTreeMap::new();
+    for e in events {
+        let stats = parts.entry((e.day, e.part)).or_insert(PartStats {
+            day: e.day,
+            part: e.part,
+            seconds: 0,
+            running: false,
+            wrong: 0,
+            runtime_ms: None,
+            commit: None,
+        });
+        match e.kind {
+            Kind::Start => {
+                started.insert((e.day, e.part), e.time);
+            }
+            Kind::Stop => {
+                if let Some(start) = started.remove(&(e.day, e.part)) {
+                    stats.seconds += e.time.saturating_sub(start);
+                }
+                stats.runtime_ms = e.runtime_ms;
+                stats.commit = e.commit.clone();
+            }
+            Kind::Wrong => stats.wrong += 1,
+        }
+    }
+    for (key, start) in started {
+        let stats = parts.get_mut(&key).unwrap();
+        stats.seconds += now.saturating_sub(start);
+        stats.running = true;
+    }
+    parts.into_values().collect()
+}
+
+fn is_running(events: &[Event], day: u8, part: u8) -> bool {
+    events
+        .iter()
+        .rev()
+        .find(|e| e.day == day && e.part == part && e.kind != Kind::Wrong)
+        .is_some_and(|e| e.kind == Kind::Start)
+}
+
+fn time_solution(solution: &solutions::Solution, part: u8) -> Result<(String, f64)> {
+    let input = puzzle_input::read(day_file(solution.day, "problem.txt"))?;
+    let params = solution.params([])?;
+    let start = Instant::now();
+    let answer = solution.solve(part, &input, &params)?;
+    Ok((answer, start.elapsed().as_secs_f64() * 1000.0))
+}
+
+fn read(path: &Path) -> Result<Vec<Event>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Bad event on line {} of {}", i + 1, path.display()))
+        })
+        .collect()
+}
+
+fn log_file() -> PathBuf {
+    std::env::var_os("AOC_LOG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../.aoc-log.jsonl"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// The short hash of HEAD, with `-dirty` when there are uncommitted changes.
+fn commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        process::Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    let hash = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+    Some(if dirty { format!("{hash}-dirty") } else { hash })
+}
+
+fn duration(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+fn event(kind: Kind, day: u8, part: u8, time: u64) -> Event {
+    Event {
+        kind,
+        day,
+        part,
+        time,
+        commit: Some(format!("c{time}")),
+        answer: None,
+        runtime_ms: (kind == Kind::Stop).then_some(1.5),
+    }
+}
+
+#[test]
+fn test_summarize() {
+    let events = [
+        event(Kind::Start, 1, 1, 100),
+        event(Kind::Wrong, 1, 1, 150),
+        event(Kind::Stop, 1, 1, 200),
+        event(Kind::Start, 1, 2, 200),
+        event(Kind::Stop, 1, 2, 230),
+        // Coming back to fix part 2 adds to its time
+        event(Kind::Start, 1, 2, 1000),
+        event(Kind::Wrong, 1, 2, 1005),
+        event(Kind::Wrong, 1, 2, 1006),
+        event(Kind::Stop, 1, 2, 1010),
+        event(Kind::Start, 2, 1, 2000),
+    ];
+    let stats = summarize(&events, 2500);
+    assert_eq!(stats.len(), 3);
+    assert_eq!((stats[0].seconds, stats[0].wrong), (100, 1));
+    assert_eq!((stats[1].seconds, stats[1].wrong), (40, 2));
+    assert_eq!(stats[1].commit.as_deref(), Some("c1010"));
+    assert_eq!(stats[1].runtime_ms, Some(1.5));
+    assert_eq!((stats[2].seconds, stats[2].running), (500, true));
+    assert_eq!(stats[2].runtime_ms, None);
+
+    assert!(is_running(&events, 2, 1));
+    assert!(!is_running(&events, 1, 2));
+}
+
+#[test]
+fn test_event_format() {
+    let line = r#"{"kind":"wrong","day":3,"part":2,"time":7,"commit":null,"answer":"42"}"#;
+    let e: Event = serde_json::from_str(line).unwrap();
+    assert_eq!((e.kind, e.answer.as_deref()), (Kind::Wrong, Some("42")));
+    assert_eq!(serde_json::to_string(&e).unwrap(), line);
+    assert_eq!(duration(3725), "1:02:05");
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod input;
+mod log;
+mod serve;
+mod viz;
+
+/// Tools for running the Advent of Code 2022 solutions.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Step through a day's simulation in the terminal (days 5, 9, 10 and 11)
+    Viz {
+        day: u8,
+        /// Simulate with the rules of this part
+        #[arg(long, default_value_t = 1)]
+        part: u8,
+        #[command(flatten)]
+        input: input::InputArgs,
+    },
+    /// Serve the solvers over HTTP on localhost
+    Serve {
+        #[arg(long, default_value_t = 2022)]
+        port: u16,
+    },
+    /// Encrypt puzzle inputs in place to problem.txt.enc (all days by default)
+    Encrypt { days: Vec<u8> },
+    /// Decrypt problem.txt.enc back to problem.txt (all days by default)
+    Decrypt { days: Vec<u8> },
+    /// Record starting, stopping or a wrong answer on a part
+    Log {
+        #[command(subcommand)]
+        action: log::Action,
+    },
+    /// Time to solve, wrong answers and runtime for each logged part
+    Stats,
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Viz { day, part, input } => viz::run(day, part, &input.read(day)?),
+        Command::Serve { port } => serve::run(port),
+        Command::Encrypt { days } => input::encrypt(&days),
+        Command::Decrypt { days } => input::decrypt(&days),
+        Command::Log { action } => log::run(action),
+        Command::Stats => log::stats(),
+    }
+}
@@ -0,0 +1,319 @@
+//! `aoc serve`: a small local HTTP API over the solvers.
+//!
+//! - `GET /days` lists the days and the parameters each one takes.
+//! - `POST /day/{n}/part/{p}` solves the request body as the puzzle input,
+//!   reading parameters from the query string, e.g. `?row=10`.
+//!
+//! Failures come back as `{"error": {"status": 404, "message": "..."}}`.
+
+use std::{
+    io::Read,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::Instant,
+};
+
+use anyhow::Result;
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Requests solved at the same time, the slower days take seconds.
+const WORKERS: usize = 4;
+
+/// The largest request body read, far beyond any puzzle input.
+const MAX_BODY: u64 = 4 << 20;
+
+pub fn run(port: u16) -> Result<()> {
+    let server = Server::http(("127.0.0.1", port))
+        .map_err(|e| anyhow::anyhow!("Could not listen on port {port}: {e}"))?;
+    if let Some(addr) = server.server_addr().to_ip() {
+        eprintln!("Listening on http://{addr}");
+    }
+
+    let server = Arc::new(server);
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            let server = server.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        _ = worker.join();
+    }
+    Ok(())
+}
+
+fn respond(mut request: Request) {
+    let reply = match read_body(request.as_reader()) {
+        Ok(body) => handle(request.method(), request.url(), &body),
+        Err(reply) => reply,
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("Could not send response: {e}");
+    }
+}
+
+/// The body, or the error to reply with if it can't be read or is over
+/// [`MAX_BODY`].
+fn read_body(reader: impl Read) -> Result<Vec<u8>, Reply> {
+    let mut body = Vec::new();
+    if let Err(e) = reader.take(MAX_BODY + 1).read_to_end(&mut body) {
+        return Err(Reply::error(
+            400,
+            format!("Could not read the request body: {e}"),
+        ));
+    }
+    if body.len() as u64 > MAX_BODY {
+        return Err(Reply::error(
+            413,
+            format!("The request body is over {MAX_BODY} bytes"),
+        ));
+    }
+    Ok(body)
+}
+
+/// Undoes the query string's `%XX` escapes and `+` for space.
+fn percent_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = text
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("Bad escape at column {} of `{text}`", i + 1))?;
+                decoded.push(byte);
+                i += 2;
+            }
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8(decoded).map_err(|_| format!("`{text}` is not UTF-8 once decoded"))
+}
+
+struct Reply {
+    status: u16,
+    body: String,
+}
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, format!("Could not serialize the response: {e}")),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(
+            status,
+            &ErrorBody {
+                error: ErrorDetail {
+                    status,
+                    message: message.into(),
+                },
+            },
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Serialize)]
+struct ErrorDetail {
+    status: u16,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Day {
+    day: u8,
+    parts: [u8; 2],
+    params: Vec<Param>,
+}
+
+#[derive(Serialize)]
+struct Param {
+    name: &'static str,
+    description: &'static str,
+    default: i64,
+}
+
+#[derive(Serialize)]
+struct Answer {
+    day: u8,
+    part: u8,
+    answer: String,
+    elapsed_ms: f64,
+}
+
+fn handle(method: &Method, url: &str, body: &[u8]) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, &segments[..]) {
+        (Method::Get, ["days"]) => days(),
+        (Method::Post, ["day", day, "part", part]) => solve(day, part, query, body),
+        (_, ["days"]) | (_, ["day", _, "part", _]) => {
+            Reply::error(405, format!("{method} is not allowed on {path}"))
+        }
+        _ => Reply::error(404, format!("Nothing at {path}")),
+    }
+}
+
+fn days() -> Reply {
+    let days: Vec<Day> = solutions::SOLUTIONS
+        .iter()
+        .map(|s| Day {
+            day: s.day,
+            parts: [1, 2],
+            params: s
+                .params
+                .iter()
+                .map(|p| Param {
+                    name: p.name,
+                    description: p.description,
+                    default: p.default,
+                })
+                .collect(),
+        })
+        .collect();
+    Reply::json(200, &days)
+}
+
+fn solve(day: &str, part: &str, query: &str, body: &[u8]) -> Reply {
+    let solution = match day
+        .parse()
+        .map_err(anyhow::Error::from)
+        .and_then(solutions::find)
+    {
+        Ok(solution) => solution,
+        Err(_) => return Reply::error(404, format!("No solution for day {day}")),
+    };
+    let part = match part.parse().map_err(anyhow::Error::from).and_then(|p| {
+        solution.part(p)?;
+        Ok(p)
+    }) {
+        Ok(part) => part,
+        Err(_) => return Reply::error(404, format!("Day {} has no part {part}", solution.day)),
+    };
+    let params: Result<Vec<(String, String)>, String> = query
+        .split('&')
+        .filter(|kv| !kv.is_empty())
+        .map(|kv| {
+            let (name, value) = kv.split_once('=').unwrap_or((kv, ""));
+            Ok((percent_decode(name)?, percent_decode(value)?))
+        })
+        .collect();
+    let params = match params {
+        Ok(params) => params,
+        Err(e) => return Reply::error(400, e),
+    };
+    let params = params.iter().map(|(k, v)| (k.as_str(), v.as_str()));
+    let params = match solution.params(params) {
+        Ok(params) => params,
+        Err(e) => return Reply::error(400, format!("{e:#}")),
+    };
+    let input = match std::str::from_utf8(body) {
+        Ok(input) => input,
+        Err(e) => return Reply::error(400, format!("Input is not UTF-8: {e}")),
+    };
+
+    let start = Instant::now();
+    let answer = catch_unwind(AssertUnwindSafe(|| solution.solve(part, input, &params)));
+    let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+    match answer {
+        Ok(Ok(answer)) => Reply::json(
+            200,
+            &Answer {
+                day: solution.day,
+                part,
+                answer,
+                elapsed_ms,
+            },
+        ),
+        Ok(Err(e)) => Reply::error(422, format!("{e:#}")),
+        Err(_) => Reply::error(500, "The solver panicked"),
+    }
+}
+
+#[cfg(test)]
+fn json(reply: &Reply) -> serde_json::Value {
+    serde_json::from_str(&reply.body).unwrap()
+}
+
+#[test]
+fn test_days() {
+    let reply = handle(&Method::Get, "/days", b"");
+    assert_eq!(reply.status, 200);
+    let days = json(&reply);
+    assert_eq!(days[0]["day"], 1);
+    assert_eq!(days[0]["parts"], serde_json::json!([1, 2]));
+    assert_eq!(days[0]["params"], serde_json::json!([]));
+}
+
+#[test]
+fn test_solve() {
+    let input = include_str!("../../day4/sample.txt");
+    let reply = handle(&Method::Post, "/day/4/part/1", input.as_bytes());
+    assert_eq!(reply.status, 200);
+    let answer = json(&reply);
+    assert_eq!(answer["answer"], "2");
+    assert_eq!(answer["day"], 4);
+    assert_eq!(answer["part"], 1);
+    assert!(answer["elapsed_ms"].as_f64().unwrap() >= 0.0);
+}
+
+#[test]
+fn test_errors() {
+    let status = |method, url, body: &[u8]| {
+        let reply = handle(&method, url, body);
+        assert_eq!(json(&reply)["error"]["status"], reply.status);
+        reply.status
+    };
+    assert_eq!(status(Method::Get, "/nowhere", b""), 404);
+    assert_eq!(status(Method::Post, "/day/99/part/1", b""), 404);
+    assert_eq!(status(Method::Post, "/day/4/part/3", b""), 404);
+    assert_eq!(status(Method::Get, "/day/4/part/1", b""), 405);
+    assert_eq!(status(Method::Post, "/days", b""), 405);
+    assert_eq!(status(Method::Post, "/day/4/part/1?row=3", b"2-4,6-8"), 400);
+    assert_eq!(status(Method::Post, "/day/4/part/1", &[0xff]), 400);
+    assert_eq!(status(Method::Post, "/day/4/part/1", b"2-4;6-8"), 422);
+    assert_eq!(status(Method::Post, "/day/15/part/1?row=%2", b""), 400);
+}
+
+#[test]
+fn test_query() {
+    assert_eq!(percent_decode("%2D5").as_deref(), Ok("-5"));
+    assert_eq!(percent_decode("a+b%20c").as_deref(), Ok("a b c"));
+    assert_eq!(percent_decode("%e2%82%ac").as_deref(), Ok("\u{20ac}"));
+    assert!(percent_decode("%zz").is_err());
+    assert!(percent_decode("%ff").is_err());
+
+    let input = "Sensor at x=0, y=0: closest beacon is at x=0, y=-2";
+    let reply = handle(&Method::Post, "/day/15/part/1?row=%2D1", input.as_bytes());
+    assert_eq!(json(&reply)["answer"], "3");
+}
+
+#[test]
+fn test_body_limit() {
+    assert_eq!(read_body(&b"2-4,6-8"[..]).ok().unwrap(), b"2-4,6-8");
+    let big = vec![b'1'; MAX_BODY as usize + 1];
+    assert_eq!(read_body(&big[..]).err().unwrap().status, 413);
+    let exact = vec![b'1'; MAX_BODY as usize];
+    assert_eq!(read_body(&exact[..]).ok().unwrap().len(), exact.len());
+}
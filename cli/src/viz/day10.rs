@@ -0,0 +1,109 @@
+use anyhow::Result;
+use aocday10::Crt;
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+};
+
+use super::Simulation;
+
+const CHECK_CYCLES: [u64; 6] = [20, 60, 100, 140, 180, 220];
+
+/// The CPU and the CRT it drives, one clock cycle per step.
+pub struct Cpu {
+    cpu: aocday10::Cpu,
+    crt: Crt,
+    /// The cycle that just ran and the value of X during it
+    last: Option<(u64, i64)>,
+    signal_strength: i64,
+}
+impl Cpu {
+    pub fn new(data: &str) -> Result<Self> {
+        Ok(Self {
+            cpu: aocday10::Cpu::new(data)?,
+            crt: Crt::default(),
+            last: None,
+            signal_strength: 0,
+        })
+    }
+}
+impl Simulation for Cpu {
+    fn step(&mut self) -> Result<bool> {
+        let Some((cycle, x)) = self.cpu.tick() else {
+            return Ok(false);
+        };
+        if CHECK_CYCLES.contains(&cycle) {
+            self.signal_strength += x * cycle as i64;
+        }
+        self.crt.draw(cycle, x);
+        self.last = Some((cycle, x));
+        Ok(true)
+    }
+
+    fn status(&self) -> String {
+        match self.last {
+            Some((cycle, x)) => format!(
+                "cycle {cycle}, X = {x} during it and {} after, signal strength sum {}",
+                self.cpu.x, self.signal_strength
+            ),
+            None => format!("X = {}, nothing has run yet", self.cpu.x),
+        }
+    }
+
+    /// The sprite position over the CRT, with the pixel the beam just drew
+    /// highlighted.
+    fn lines(&self, _area: Rect) -> Vec<Line<'static>> {
+        let sprite = (0..Crt::WIDTH as i64)
+            .map(|column| {
+                if (column - self.cpu.x).abs() <= 1 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        let mut lines = vec![
+            Line::from(format!("Sprite {sprite}")),
+            Line::from(""),
+        ];
+
+        let rows = self.crt.rows.len();
+        for (i, row) in self.crt.rows.iter().enumerate() {
+            let mut spans = vec![Span::raw("CRT    ")];
+            let (drawn, beam) = if i + 1 == rows {
+                row.split_at(row.len() - 1)
+            } else {
+                (&row[..], &[][..])
+            };
+            spans.push(Span::raw(drawn.iter().collect::<String>()));
+            spans.push(Span::styled(
+                beam.iter().collect::<String>(),
+                Style::new().reversed(),
+            ));
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+#[test]
+fn test_lines() -> Result<()> {
+    let mut cpu = Cpu::new("noop\naddx 3\naddx -5")?;
+    cpu.step()?;
+    cpu.step()?;
+    assert_eq!(
+        super::text(&cpu, 50, 10),
+        vec![
+            format!("Sprite ###{}", ".".repeat(37)),
+            String::new(),
+            "CRT    ##".to_string(),
+        ]
+    );
+    while cpu.step()? {}
+    assert_eq!(
+        cpu.status(),
+        "cycle 5, X = 4 during it and -1 after, signal strength sum 0"
+    );
+    Ok(())
+}
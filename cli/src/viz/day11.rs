@@ -0,0 +1,93 @@
+use anyhow::Result;
+use aocday11::{KeepAway as Game, Throw};
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+};
+
+use super::Simulation;
+
+/// The monkeys playing keep away, one throw per step.
+pub struct KeepAway {
+    game: Game,
+    rounds: usize,
+    last: Option<Throw>,
+}
+impl KeepAway {
+    pub fn new(data: &str, part: u8) -> Result<Self> {
+        let (rounds, worry) = match part {
+            1 => (20, 3),
+            _ => (10000, 1),
+        };
+        Ok(Self {
+            game: Game::new(data, rounds, worry)?,
+            rounds,
+            last: None,
+        })
+    }
+}
+impl Simulation for KeepAway {
+    fn step(&mut self) -> Result<bool> {
+        self.last = self.game.step()?;
+        Ok(self.last.is_some())
+    }
+
+    fn status(&self) -> String {
+        let round = format!(
+            "round {}/{}",
+            (self.game.round + 1).min(self.rounds),
+            self.rounds
+        );
+        match self.last {
+            Some(t) => format!(
+                "{round}: monkey {} threw {} to monkey {}",
+                t.from, t.value, t.to
+            ),
+            None => round,
+        }
+    }
+
+    /// Every monkey's items, with the last thrower and catcher highlighted.
+    fn lines(&self, _area: Rect) -> Vec<Line<'static>> {
+        self.game
+            .monkeys
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let items = m
+                    .items
+                    .iter()
+                    .map(|item| item.value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let line = Line::from(format!(
+                    "Monkey {i} ({:>6} inspected): {items}",
+                    self.game.inspections[i]
+                ));
+                match self.last {
+                    Some(t) if t.from == i => line.style(Style::new().yellow()),
+                    Some(t) if t.to == i => line.style(Style::new().green()),
+                    _ => line,
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_lines() -> Result<()> {
+    let mut game = KeepAway::new(include_str!("../../../day11/sample.txt"), 1)?;
+    game.step()?;
+    assert_eq!(
+        super::text(&game, 80, 10),
+        vec![
+            "Monkey 0 (     1 inspected): 98",
+            "Monkey 1 (     0 inspected): 54, 65, 75, 74",
+            "Monkey 2 (     0 inspected): 79, 60, 97",
+            "Monkey 3 (     0 inspected): 74, 500",
+        ]
+    );
+    assert_eq!(game.status(), "round 1/20: monkey 0 threw 500 to monkey 3");
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use aocday5::CrateMover;
+use ratatui::{layout::Rect, text::Line};
+
+use super::Simulation;
+
+/// The crane, one command per step.
+pub struct Crane(aocday5::Crane);
+impl Crane {
+    pub fn new(data: &str, part: u8) -> Result<Self> {
+        let model = match part {
+            1 => CrateMover::Model9000,
+            _ => CrateMover::Model9001,
+        };
+        Ok(Self(aocday5::Crane::new(data, model)?))
+    }
+}
+impl Simulation for Crane {
+    fn step(&mut self) -> Result<bool> {
+        Ok(self.0.step()?.is_some())
+    }
+
+    fn status(&self) -> String {
+        let crane = &self.0;
+        let done = format!("{}/{}", crane.next, crane.commands.len());
+        match crane.next.checked_sub(1).and_then(|i| crane.commands.get(i)) {
+            Some(c) => format!(
+                "{done}: move {} from {} to {}, tops {}",
+                c.count,
+                c.from + 1,
+                c.to + 1,
+                crane.stacks.across()
+            ),
+            None => format!("{done}, tops {}", crane.stacks.across()),
+        }
+    }
+
+    /// The stacks drawn like the puzzle, keeping the tops in view when they
+    /// are taller than the screen.
+    fn lines(&self, area: Rect) -> Vec<Line<'static>> {
+        let stacks = &self.0.stacks.stacks;
+        let tallest = stacks.iter().map(|s| s.items.len()).max().unwrap_or(0);
+        let shown = tallest.min((area.height as usize).saturating_sub(1));
+
+        let mut lines = Vec::new();
+        for level in (tallest - shown..tallest).rev() {
+            let row = stacks
+                .iter()
+                .map(|s| match s.items.get(level) {
+                    Some(item) => format!("[{item}]"),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(Line::from(row));
+        }
+        let numbers = (1..=stacks.len())
+            .map(|n| format!(" {n} "))
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(Line::from(numbers));
+        lines
+    }
+}
+
+#[test]
+fn test_lines() -> Result<()> {
+    let mut crane = Crane::new(include_str!("../../../day5/sample.txt"), 1)?;
+    assert_eq!(
+        super::text(&crane, 40, 10),
+        vec!["    [D]    ", "[N] [C]    ", "[Z] [M] [P]", " 1   2   3 "]
+    );
+    crane.step()?;
+    assert_eq!(crane.status(), "1/4: move 1 from 2 to 1, tops DCP");
+    // Only the top two levels fit
+    assert_eq!(
+        super::text(&crane, 40, 3),
+        vec!["[D]        ", "[N] [C]    ", " 1   2   3 "]
+    );
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use geometry::Point;
+use ratatui::{layout::Rect, text::Line};
+
+use super::Simulation;
+
+/// The rope, one square of head movement per step.
+pub struct Rope(aocday9::Rope);
+impl Rope {
+    pub fn new(data: &str, part: u8) -> Result<Self> {
+        let knots = match part {
+            1 => 2,
+            _ => 10,
+        };
+        Ok(Self(aocday9::Rope::new(data, knots)?))
+    }
+
+    /// What to draw at `p`, knots nearer the head win like the puzzle.
+    fn cell(&self, p: Point<i64>) -> char {
+        let knots = &self.0.knots;
+        match knots.iter().position(|k| *k == p) {
+            Some(0) => 'H',
+            Some(i) if knots.len() == 2 => {
+                debug_assert_eq!(i, 1);
+                'T'
+            }
+            Some(i) => char::from_digit(i as u32, 36).unwrap_or('?'),
+            None if p == Point::new(0, 0) => 's',
+            None if self.0.visited.contains(&p) => '#',
+            None => '.',
+        }
+    }
+}
+impl Simulation for Rope {
+    fn step(&mut self) -> Result<bool> {
+        Ok(self.0.step())
+    }
+
+    fn status(&self) -> String {
+        let head = self.0.knots[0];
+        format!(
+            "head at ({}, {}), tail has visited {} positions",
+            head.x,
+            head.y,
+            self.0.visited.len()
+        )
+    }
+
+    /// A window on the rope centered on the head.
+    fn lines(&self, area: Rect) -> Vec<Line<'static>> {
+        let head = self.0.knots[0];
+        let (width, height) = (i64::from(area.width), i64::from(area.height));
+        let top_left = Point::new(head.x - width / 2, head.y - height / 2);
+        (0..height)
+            .map(|dy| {
+                (0..width)
+                    .map(|dx| self.cell(Point::new(top_left.x + dx, top_left.y + dy)))
+                    .collect::<String>()
+                    .into()
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_lines() -> Result<()> {
+    let mut rope = Rope::new("R 2\nU 1", 1)?;
+    while rope.step()? {}
+    assert_eq!(super::text(&rope, 5, 3), vec![".....", "..H..", "sT..."]);
+    assert_eq!(rope.status(), "head at (2, -1), tail has visited 2 positions");
+    Ok(())
+}
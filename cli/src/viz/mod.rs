@@ -0,0 +1,191 @@
+//! `aoc viz`: steps through a day's simulation in the terminal.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+mod day10;
+mod day11;
+mod day5;
+mod day9;
+
+/// A puzzle simulation that can be advanced one step at a time and drawn.
+pub trait Simulation {
+    /// Advances a single step, returning false once there is nothing left to do.
+    fn step(&mut self) -> Result<bool>;
+    /// A one line summary of where the simulation is up to.
+    fn status(&self) -> String;
+    /// The current state, drawn to fit in `area`.
+    fn lines(&self, area: Rect) -> Vec<Line<'static>>;
+}
+
+fn simulation(day: u8, part: u8, data: &str) -> Result<Box<dyn Simulation>> {
+    if part != 1 && part != 2 {
+        anyhow::bail!("There is no part {part}");
+    }
+    Ok(match day {
+        5 => Box::new(day5::Crane::new(data, part)?),
+        9 => Box::new(day9::Rope::new(data, part)?),
+        10 => Box::new(day10::Cpu::new(data)?),
+        11 => Box::new(day11::KeepAway::new(data, part)?),
+        _ => anyhow::bail!("Day {day} has no visualization, try 5, 9, 10 or 11"),
+    })
+}
+
+pub fn run(day: u8, part: u8, data: &str) -> Result<()> {
+    // Build the simulation before taking over the terminal so bad input is
+    // reported normally.
+    let sim = simulation(day, part, data)?;
+    let mut app = App {
+        day,
+        part,
+        data,
+        sim,
+        steps: 0,
+        playing: false,
+        finished: false,
+        speed: 4,
+    };
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+const FRAME: Duration = Duration::from_millis(16);
+const MAX_SPEED: u64 = 1 << 20;
+
+struct App<'a> {
+    day: u8,
+    part: u8,
+    data: &'a str,
+    sim: Box<dyn Simulation>,
+    steps: u64,
+    playing: bool,
+    finished: bool,
+    /// Steps per second while playing
+    speed: u64,
+}
+impl App<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        let mut last = Instant::now();
+        // Fractional steps carried between frames at low speeds
+        let mut owed = 0.0f64;
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(FRAME)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char(' ') => self.playing = !self.playing && !self.finished,
+                            KeyCode::Right | KeyCode::Char('n') => {
+                                self.playing = false;
+                                self.advance(1)?;
+                            }
+                            KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => {
+                                self.speed = (self.speed * 2).min(MAX_SPEED)
+                            }
+                            KeyCode::Down | KeyCode::Char('-') => {
+                                self.speed = (self.speed / 2).max(1)
+                            }
+                            KeyCode::Char('r') => self.restart()?,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            let now = Instant::now();
+            if self.playing {
+                owed += now.duration_since(last).as_secs_f64() * self.speed as f64;
+                let due = owed.floor();
+                owed -= due;
+                self.advance(due as u64)?;
+            } else {
+                owed = 0.0;
+            }
+            last = now;
+        }
+    }
+
+    fn advance(&mut self, steps: u64) -> Result<()> {
+        for _ in 0..steps {
+            if self.finished {
+                break;
+            }
+            if self.sim.step()? {
+                self.steps += 1;
+            } else {
+                self.finished = true;
+                self.playing = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn restart(&mut self) -> Result<()> {
+        self.sim = simulation(self.day, self.part, self.data)?;
+        self.steps = 0;
+        self.finished = false;
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let title = format!(" Day {} part {} ", self.day, self.part);
+        frame.render_widget(
+            Paragraph::new(self.sim.status()).block(Block::bordered().title(title)),
+            header,
+        );
+
+        let block = Block::bordered();
+        let lines = self.sim.lines(block.inner(body));
+        frame.render_widget(Paragraph::new(lines).block(block), body);
+
+        let state = if self.finished {
+            "finished"
+        } else if self.playing {
+            "playing"
+        } else {
+            "paused"
+        };
+        frame.render_widget(
+            Paragraph::new(format!(
+                " {state} | step {} | {}/s | space play/pause, \u{2192} step, +/- speed, r restart, q quit",
+                self.steps, self.speed
+            )),
+            footer,
+        );
+    }
+}
+
+/// Renders a simulation to plain text, for tests.
+#[cfg(test)]
+fn text(sim: &dyn Simulation, width: u16, height: u16) -> Vec<String> {
+    sim.lines(Rect::new(0, 0, width, height))
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[test]
+fn test_unknown_day() {
+    assert!(simulation(3, 1, "").is_err());
+    assert!(simulation(5, 3, "").is_err());
+}
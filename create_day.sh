@@ -12,5 +12,5 @@ fi
 
 cp -r template $day
 for file in `find $day -type f`; do
-    sed -i "s/aoc/aoc$day/g" $file
+    sed -i "s/aoctemplate/aoc$day/g" $file
 done
\ No newline at end of file
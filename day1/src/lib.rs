@@ -1,12 +1,21 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
 use anyhow::Result;
 
+pub mod stats;
+pub use stats::Stats;
+
 #[derive(Clone)]
 pub struct Elf {
     pub food: Vec<u32>,
 }
 impl Elf {
-    pub fn total_cal(&self) -> u32 {
-        self.food.iter().sum()
+    /// In a `u64`, which no realistic number of `u32` items can overflow,
+    /// so elves built by hand total as safely as those [`ElfReader`] reads.
+    pub fn total_cal(&self) -> u64 {
+        self.food.iter().map(|&cal| u64::from(cal)).sum()
     }
 }
 pub struct CalorieElf<'a>(&'a Elf);
@@ -41,41 +50,268 @@ impl Elves {
         elves.sort_by(|a,b| a.total_cal().cmp(&b.total_cal()));
         elves
     }
+
+    /// The `n` elves carrying the most calories, most first, each with its
+    /// index in the list.  Elves with equal totals keep their original order,
+    /// so the earlier elf wins a place at the cut-off.
+    ///
+    /// Only `n` elves are held at once, in a min-heap whose top is the one to
+    /// drop next, so this is `O(len log n)` and copies no food.
+    pub fn top_n(&self, n: usize) -> Vec<(usize, &Elf)> {
+        let mut top = TopTotals::new(n);
+        for (index, elf) in self.0.iter().enumerate() {
+            top.push(index, elf.total_cal());
+        }
+        top.top().into_iter().map(|(index, _)| (index, &self.0[index])).collect()
+    }
+
+    /// How calories and items are spread across the elves, `None` if there
+    /// are none.
+    pub fn stats(&self) -> Option<Stats> {
+        Stats::of(self.0.iter().map(|e| (e.total_cal(), e.food.len())))
+    }
 }
 
-pub fn read_food(file: &str) -> Result<Vec<Elf>> {
-    let contents= std::fs::read_to_string(file)?;
-    read_food_from_string(&contents)
+/// The biggest totals seen so far, kept up to date one elf at a time so a
+/// stream of elves never has to be held in memory.
+pub struct TopTotals {
+    k: usize,
+    /// Bigger keys rank higher: more calories, then the earlier index
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+    max: Option<(usize, u64)>,
+    elves: usize,
 }
+impl TopTotals {
+    /// Keeps the top `k`; the maximum is tracked whatever `k` is.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+            max: None,
+            elves: 0,
+        }
+    }
 
-fn read_food_from_string(contents: &str) -> Result<Vec<Elf>> {
-    let lines = contents.lines();
-    split_on_blank_line(lines).map(|food| Ok(Elf { food: food? })).collect()
+    pub fn push(&mut self, index: usize, total: u64) {
+        self.elves += 1;
+        if self.max.is_none_or(|(_, max)| total > max) {
+            self.max = Some((index, total));
+        }
+        if self.k > 0 {
+            self.heap.push(Reverse((total, Reverse(index))));
+            if self.heap.len() > self.k {
+                self.heap.pop();
+            }
+        }
+    }
+
+    /// Pushes every tally, stopping at the first error.
+    pub fn read(&mut self, tallies: impl IntoIterator<Item = Result<Tally>>) -> Result<()> {
+        for tally in tallies {
+            let tally = tally?;
+            self.push(tally.index, tally.total);
+        }
+        Ok(())
+    }
+
+    /// How many elves have been pushed.
+    pub fn elves(&self) -> usize {
+        self.elves
+    }
+
+    /// The index and total of the first elf with the most calories.
+    pub fn max(&self) -> Option<(usize, u64)> {
+        self.max
+    }
+
+    /// Up to `k` indices and totals, most calories first.
+    pub fn top(&self) -> Vec<(usize, u64)> {
+        self.heap
+            .clone()
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect()
+    }
+}
+
+/// What to do with a line that is not a number.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Stop with an error naming the line
+    #[default]
+    Strict,
+    /// Skip it and carry on, keeping a note of it
+    Lenient,
+}
+
+/// A line skipped in [`Mode::Lenient`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadLine {
+    /// Counting from 1
+    pub line: usize,
+    pub text: String,
+    pub error: String,
+}
+impl std::fmt::Display for BadLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {} `{}`", self.line, self.error, self.text)
+    }
+}
+
+/// One elf's total and how many items make it up, without the items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tally {
+    /// Counting from 0, in the order read
+    pub index: usize,
+    pub total: u64,
+    pub items: usize,
 }
 
-fn split_on_blank_line<'a>(
-    mut lines: impl Iterator<Item = &'a str> + 'a,
-) -> impl Iterator<Item = Result<Vec<u32>>> + 'a {
-    let block_of_lines = move || {
-        let mut data = Vec::new();
-        while let Some(line) = lines.next() {
-            if line.is_empty() {
+/// Reads elves one at a time from lines of calories, holding only the elf
+/// being read, or with [`ElfReader::totals`] only its running total.
+///
+/// Elves are separated by one or more blank lines, where a line of only
+/// whitespace counts as blank, and whitespace around a number is ignored.
+/// Totals are kept in a `u64`, and an elf whose total somehow outgrows even
+/// that is an error naming the elf and its lines.
+pub struct ElfReader<R> {
+    reader: R,
+    /// The line being read, reused so lines cost no allocation each
+    buf: String,
+    mode: Mode,
+    /// Lines read so far, to say where a bad one is
+    line: usize,
+    /// Elves read so far
+    elves: usize,
+    skipped: Vec<BadLine>,
+}
+impl<R: BufRead> ElfReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_mode(reader, Mode::Strict)
+    }
+
+    pub fn with_mode(reader: R, mode: Mode) -> Self {
+        Self {
+            reader,
+            buf: String::new(),
+            mode,
+            line: 0,
+            elves: 0,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// The lines skipped so far in [`Mode::Lenient`].  An elf with nothing
+    /// but bad lines is skipped entirely.
+    pub fn skipped(&self) -> &[BadLine] {
+        &self.skipped
+    }
+
+    /// The elves' tallies rather than the elves, summed while reading so
+    /// memory stays constant however much each elf carries.
+    pub fn totals(&mut self) -> impl Iterator<Item = Result<Tally>> + '_ {
+        std::iter::from_fn(move || self.next_group(None))
+    }
+
+    /// Reads up to the end of the next elf, adding its items to `food` if
+    /// given.
+    fn next_group(&mut self, mut food: Option<&mut Vec<u32>>) -> Option<Result<Tally>> {
+        let mut items = 0;
+        let (mut first_line, mut last_line) = (0, 0);
+        let mut total = Some(0u64);
+        loop {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+            self.line += 1;
+            let text = self.buf.trim();
+            if text.is_empty() {
+                if items == 0 {
+                    continue;
+                }
                 break;
             }
-            let value = line.parse();
-            match value {
-                Ok(value) => data.push(value),
-                Err(e) => return Some(Err(anyhow::anyhow!("Could not parse line: {line} {e}")))
+            match text.parse() {
+                Ok(value) => {
+                    if items == 0 {
+                        first_line = self.line;
+                    }
+                    last_line = self.line;
+                    items += 1;
+                    total = total.and_then(|total| total.checked_add(u64::from(value)));
+                    if let Some(food) = food.as_mut() {
+                        food.push(value);
+                    }
+                }
+                Err(e) if self.mode == Mode::Lenient => self.skipped.push(BadLine {
+                    line: self.line,
+                    text: text.to_string(),
+                    error: e.to_string(),
+                }),
+                Err(e) => {
+                    return Some(Err(anyhow::anyhow!(
+                        "Could not parse line {}: {text} {e}",
+                        self.line
+                    )))
+                }
             }
         }
-        if data.is_empty() {
-            None
-        } else {
-            Some(Ok(data))
+        if items == 0 {
+            return None;
         }
-    };
-    // turn block of lines fn into an iterator
-    std::iter::from_fn(block_of_lines)
+        let index = self.elves;
+        self.elves += 1;
+        let Some(total) = total else {
+            return Some(Err(anyhow::anyhow!(
+                "Elf {index} on lines {first_line}-{last_line} carries more than {} calories",
+                u64::MAX
+            )));
+        };
+        Some(Ok(Tally { index, total, items }))
+    }
+}
+impl<R: BufRead> Iterator for ElfReader<R> {
+    type Item = Result<Elf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut food = Vec::new();
+        self.next_group(Some(&mut food))
+            .map(|tally| tally.map(|_| Elf { food }))
+    }
+}
+
+/// The top `k` totals of every elf in `reader`, in constant memory.
+pub fn top_totals(reader: impl BufRead, k: usize) -> Result<TopTotals> {
+    let mut top = TopTotals::new(k);
+    top.read(ElfReader::new(reader).totals())?;
+    Ok(top)
+}
+
+pub fn read_food(file: &str) -> Result<Vec<Elf>> {
+    let contents = puzzle_input::read(file)?;
+    read_food_from_string(&contents)
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let elves = Elves(read_food_from_string(data)?);
+    let max_elf = elves
+        .max_calorie_elf()
+        .ok_or_else(|| anyhow::anyhow!("No max elf"))?;
+    Ok(max_elf.total_cal().to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let elves = Elves(read_food_from_string(data)?);
+    let sum: u64 = elves.top_n(3).into_iter().map(|(_, e)| e.total_cal()).sum();
+    Ok(sum.to_string())
+}
+
+fn read_food_from_string(contents: &str) -> Result<Vec<Elf>> {
+    ElfReader::new(contents.as_bytes()).collect()
 }
 
 #[cfg(test)]
@@ -108,7 +344,110 @@ fn test_sorted() {
     let elves = Elves(elves());
     let mut sorted = elves.sorted_by_calorie();
     sorted.reverse();
-    let cals_top_3 : u32 = sorted.into_iter().map(|e| e.total_cal()).take(3).sum();
+    let cals_top_3 : u64 = sorted.into_iter().map(|e| e.total_cal()).take(3).sum();
 
     assert_eq!(cals_top_3,45000);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_top_n() {
+    let elves = Elves(elves());
+    let top: Vec<(usize, u64)> = elves.top_n(3).into_iter().map(|(i, e)| (i, e.total_cal())).collect();
+    assert_eq!(top, [(3, 24000), (2, 11000), (4, 10000)]);
+    assert_eq!(elves.top_n(10).len(), 5);
+    assert!(elves.top_n(0).is_empty());
+
+    // Ties go to the earlier elf
+    let tied = Elves([5, 7, 5, 7, 5].iter().map(|&c| Elf { food: vec![c] }).collect());
+    let order: Vec<usize> = tied.top_n(4).into_iter().map(|(i, _)| i).collect();
+    assert_eq!(order, [1, 3, 0, 2]);
+}
+
+#[test]
+fn test_stream() -> Result<()> {
+    let top = top_totals(include_str!("../sample.txt").as_bytes(), 3)?;
+    assert_eq!(top.elves(), 5);
+    assert_eq!(top.max(), Some((3, 24000)));
+    assert_eq!(top.top(), [(3, 24000), (2, 11000), (4, 10000)]);
+
+    assert_eq!(top_totals("7\n\n7\n".as_bytes(), 0)?.max(), Some((0, 7)));
+    assert!(top_totals("".as_bytes(), 3)?.max().is_none());
+    let err = top_totals("1\n2\n\nx\n".as_bytes(), 3).err().unwrap();
+    assert!(err.to_string().starts_with("Could not parse line 4: x"));
+    Ok(())
+}
+
+#[test]
+fn test_overflow() {
+    let max = u32::MAX;
+    // Elves carrying more than a u32 are read, not rejected
+    let data = format!("1\n\n{max}\n\n5\n{max}\n");
+    let totals: Vec<u64> = read_food_from_string(&data).unwrap().iter().map(|e| e.total_cal()).collect();
+    assert_eq!(totals, [1, u64::from(max), u64::from(max) + 5]);
+    let tallies: Vec<Tally> = ElfReader::new(data.as_bytes()).totals().collect::<Result<_>>().unwrap();
+    assert_eq!(tallies[2], Tally { index: 2, total: u64::from(max) + 5, items: 2 });
+
+    // Elves built by hand are not checked, but still total without panicking
+    let elves = Elves(vec![Elf { food: vec![max, 1] }, Elf { food: vec![max; 3] }]);
+    assert_eq!(elves.max_calorie_elf().unwrap().total_cal(), 3 * u64::from(max));
+    let top: Vec<(usize, u64)> = elves.top_n(3).into_iter().map(|(i, e)| (i, e.total_cal())).collect();
+    assert_eq!(top, [(1, 3 * u64::from(max)), (0, u64::from(max) + 1)]);
+    assert_eq!(elves.stats().unwrap().totals.max, 3 * u64::from(max));
+
+    // Totals that fit can still add up to more than a u32 in part 2
+    let data = format!("{max}\n\n{max}\n\n{max}\n");
+    assert_eq!(part2(&data).unwrap(), (3 * u64::from(max)).to_string());
+}
+
+#[test]
+fn test_grouping() -> Result<()> {
+    let data = "\n\n1\n2 \n\n\n \t\n 3\n\n\n";
+    let totals: Vec<u64> = read_food_from_string(data)?.iter().map(|e| e.total_cal()).collect();
+    assert_eq!(totals, [3, 3]);
+
+    let data = "1\nx\n\ny\n\n4\n-5\n";
+    let err = read_food_from_string(data).err().unwrap();
+    assert_eq!(err.to_string(), "Could not parse line 2: x invalid digit found in string");
+
+    let mut reader = ElfReader::with_mode(data.as_bytes(), Mode::Lenient);
+    let totals: Vec<u64> = reader.by_ref().map(|e| e.map(|e| e.total_cal())).collect::<Result<_>>()?;
+    assert_eq!(totals, [1, 4]);
+    let lines: Vec<usize> = reader.skipped().iter().map(|b| b.line).collect();
+    assert_eq!(lines, [2, 4, 7]);
+    assert_eq!(reader.skipped()[0].to_string(), "line 2: invalid digit found in string `x`");
+
+    // Bad lines inside an elf are skipped without splitting it
+    let data = format!("\n\nz\n{}\n\nz\n1\n", u32::MAX);
+    let mut reader = ElfReader::with_mode(data.as_bytes(), Mode::Lenient);
+    assert_eq!(reader.next().unwrap()?.total_cal(), u64::from(u32::MAX));
+    let data = format!("\n\n{}\nz\n1\n", u32::MAX);
+    let mut reader = ElfReader::with_mode(data.as_bytes(), Mode::Lenient);
+    assert_eq!(reader.next().unwrap()?.total_cal(), u64::from(u32::MAX) + 1);
+    assert_eq!(reader.skipped().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_stats() {
+    let stats = Elves(elves()).stats().unwrap();
+    assert_eq!(stats.totals.max, 24000);
+    assert_eq!(stats.items.mean, 2.0);
+    assert_eq!(stats.items.median, 2.0);
+    assert!(Elves(Vec::new()).stats().is_none());
+}
+
+#[test]
+fn test_totals() -> Result<()> {
+    let data = "1\n2\n\n\nx\n\n4\n";
+    let mut reader = ElfReader::with_mode(data.as_bytes(), Mode::Lenient);
+    let tallies: Vec<Tally> = reader.totals().collect::<Result<_>>()?;
+    assert_eq!(
+        tallies,
+        [
+            Tally { index: 0, total: 3, items: 2 },
+            Tally { index: 1, total: 4, items: 1 },
+        ]
+    );
+    assert_eq!(reader.skipped().len(), 1);
+    Ok(())
+}
@@ -1,7 +1,55 @@
+use std::io::BufRead;
+
 use anyhow::Result;
+use day1::stats::histogram;
+use day1::{BadLine, ElfReader, Elves, Mode, Stats, TopTotals};
 
+/// Reads `problem.txt`, or with `--stdin` streams elves from standard input
+/// without holding them all in memory.  With `--lenient` lines that are not
+/// numbers are skipped and listed at the end rather than stopping the run.
+/// `--stats` prints how calories are spread across the elves instead.
 fn main() -> Result<()> {
-    let elves = day1::Elves(day1::read_food("problem.txt")?);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let flag = |name: &str| args.iter().any(|arg| arg == name);
+    let mode = if flag("--lenient") { Mode::Lenient } else { Mode::Strict };
+
+    if flag("--stats") {
+        let input: Box<dyn BufRead> = if flag("--stdin") {
+            Box::new(std::io::stdin().lock())
+        } else {
+            Box::new(std::io::Cursor::new(puzzle_input::read("problem.txt")?))
+        };
+        let mut reader = ElfReader::with_mode(input, mode);
+        let mut totals = Vec::new();
+        let mut items = Vec::new();
+        for tally in reader.totals() {
+            let tally = tally?;
+            totals.push(tally.total);
+            items.push(tally.items);
+        }
+        let stats = Stats::of(totals.iter().copied().zip(items))
+            .ok_or_else(|| anyhow::anyhow!("No elves"))?;
+        println!("{stats}\n");
+        print!("{}", histogram(&totals, 10, 50));
+        report(reader.skipped());
+        return Ok(());
+    }
+
+    if flag("--stdin") {
+        let mut reader = ElfReader::with_mode(std::io::stdin().lock(), mode);
+        let mut top = TopTotals::new(3);
+        top.read(reader.totals())?;
+        let (_, max) = top.max().ok_or_else(|| anyhow::anyhow!("No max elf"))?;
+        println!("{max}");
+        let sum : u64 = top.top().into_iter().map(|(_, total)| total).sum();
+        println!("Sum top 3 = {sum}");
+        report(reader.skipped());
+        return Ok(());
+    }
+
+    let contents = puzzle_input::read("problem.txt")?;
+    let mut reader = ElfReader::with_mode(contents.as_bytes(), mode);
+    let elves = Elves(reader.by_ref().collect::<Result<_>>()?);
 
     println!(
         "{}",
@@ -11,11 +59,19 @@ fn main() -> Result<()> {
             .total_cal()
     );
 
-    let mut top3 = elves.sorted_by_calorie();
-    top3.reverse();
-    let sum : u32 = top3.into_iter().map(|e| e.total_cal()).take(3).sum();
+    let sum : u64 = elves.top_n(3).into_iter().map(|(_, e)| e.total_cal()).sum();
 
     println!("Sum top 3 = {sum}");
+    report(reader.skipped());
 
     Ok(())
 }
+
+fn report(skipped: &[BadLine]) {
+    if !skipped.is_empty() {
+        eprintln!("Skipped {} bad lines:", skipped.len());
+        for bad in skipped {
+            eprintln!("  {bad}");
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+/// The percentiles reported besides the median.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// How one number is spread across the elves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Each of [`PERCENTILES`] with its value
+    pub percentiles: Vec<(u8, f64)>,
+    /// Of the whole population, not an estimate from a sample
+    pub std_dev: f64,
+}
+impl Summary {
+    /// `None` if there are no values.
+    pub fn of(values: &[u64]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let count = sorted.len();
+        let mean = sorted.iter().map(|&v| v as f64).sum::<f64>() / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        Some(Self {
+            count,
+            min,
+            max,
+            mean,
+            median: percentile(&sorted, 50),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| (p, percentile(&sorted, p)))
+                .collect(),
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+/// The `p`th percentile of sorted, non-empty `values`, interpolating
+/// between the two nearest when it falls between them.
+fn percentile(sorted: &[u64], p: u8) -> f64 {
+    let rank = f64::from(p) / 100.0 * (sorted.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - below as f64;
+    sorted[below] as f64 + (sorted[above] as f64 - sorted[below] as f64) * fraction
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "count {}, min {}, max {}, mean {:.1}, median {:.1}, std dev {:.1}",
+            self.count, self.min, self.max, self.mean, self.median, self.std_dev
+        )?;
+        let percentiles: Vec<String> = self
+            .percentiles
+            .iter()
+            .map(|(p, value)| format!("p{p} {value:.1}"))
+            .collect();
+        write!(f, "  {}", percentiles.join(", "))
+    }
+}
+
+/// Calories and items per elf.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub totals: Summary,
+    pub items: Summary,
+}
+impl Stats {
+    /// From each elf's total and number of items, `None` if there are no
+    /// elves.
+    pub fn of(elves: impl IntoIterator<Item = (u64, usize)>) -> Option<Self> {
+        let (totals, items): (Vec<u64>, Vec<u64>) = elves
+            .into_iter()
+            .map(|(total, items)| (total, items as u64))
+            .unzip();
+        Some(Self {
+            totals: Summary::of(&totals)?,
+            items: Summary::of(&items)?,
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Calories per elf: {}", self.totals)?;
+        write!(f, "Items per elf: {}", self.items)
+    }
+}
+
+/// `values` counted into `buckets` ranges of equal width from the smallest
+/// to the largest, one line per range with a bar up to `width` long.
+pub fn histogram(values: &[u64], buckets: usize, width: usize) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let buckets = buckets.max(1);
+    let span = u128::from(max - min) + 1;
+    let bucket_of = |v: u64| (u128::from(v - min) * buckets as u128 / span) as usize;
+    let start_of = |b: usize| min as u128 + (b as u128 * span).div_ceil(buckets as u128);
+
+    let mut counts = vec![0usize; buckets];
+    for &v in values {
+        counts[bucket_of(v)] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0);
+    let label_width = max.to_string().len();
+
+    let mut out = String::new();
+    for (b, &count) in counts.iter().enumerate() {
+        let (low, high) = (start_of(b), start_of(b + 1) - 1);
+        if low > high {
+            // More buckets than distinct values
+            continue;
+        }
+        let bar = match count {
+            0 => 0,
+            _ => (count * width).div_ceil(most),
+        };
+        out.push_str(&format!(
+            "{low:>label_width$} - {high:>label_width$} | {} {count}\n",
+            "#".repeat(bar)
+        ));
+    }
+    out
+}
+
+#[test]
+fn test_summary() {
+    let summary = Summary::of(&[10000, 24000, 4000, 11000, 6000]).unwrap();
+    assert_eq!((summary.count, summary.min, summary.max), (5, 4000, 24000));
+    assert_eq!(summary.mean, 11000.0);
+    assert_eq!(summary.median, 10000.0);
+    assert_eq!(summary.percentiles[1], (25, 6000.0));
+    assert_eq!(summary.percentiles[3], (90, 18800.0));
+    assert!((summary.std_dev - 48_800_000f64.sqrt()).abs() < 1e-9);
+
+    assert_eq!(Summary::of(&[7]).unwrap().percentiles[4], (99, 7.0));
+    assert!(Summary::of(&[]).is_none());
+    assert!(Stats::of([]).is_none());
+}
+
+#[test]
+fn test_histogram() {
+    let values = [10000, 24000, 4000, 11000, 6000];
+    assert_eq!(
+        histogram(&values, 4, 10),
+        " 4000 -  9000 | ########## 2\n 9001 - 14000 | ########## 2\n14001 - 19000 |  0\n19001 - 24000 | ##### 1\n"
+    );
+    assert_eq!(histogram(&[5, 5], 3, 4), "5 - 5 | #### 2\n");
+    assert_eq!(histogram(&[], 3, 4), "");
+}
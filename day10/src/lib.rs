@@ -1,112 +1,120 @@
 #![allow(unused)]
-use std::{cell::{Cell, RefCell}, borrow::BorrowMut};
-
 use anyhow::Result;
-
-struct CPU {
-    x: i64,
-    cycle: u64
+use line_format::LineFormat;
+
+pub struct Cpu {
+    pub x: i64,
+    /// The cycle that the next tick will run
+    pub cycle: u64,
+    program: Vec<Instruction>,
+    pc: usize,
+    adding: bool,
 }
-impl CPU {
-    fn new() -> Self {
-        Self {
+impl Cpu {
+    pub fn new(data: &str) -> Result<Self> {
+        let program = data
+            .lines()
+            .map(Instruction::try_from)
+            .collect::<Result<_>>()?;
+        Ok(Self {
             x: 1,
-            cycle: 1
-        }
+            cycle: 1,
+            program,
+            pc: 0,
+            adding: false,
+        })
     }
-    fn eval(&mut self, instruction: &Instruction, on_cycle: impl Fn(u64,i64)) {
+
+    /// Runs one clock cycle.  Returns the cycle number with the value of X
+    /// during that cycle, or `None` once the program has finished.
+    pub fn tick(&mut self) -> Option<(u64, i64)> {
+        let instruction = self.program.get(self.pc)?;
+        let during = (self.cycle, self.x);
         match instruction {
-            Instruction::Noop => {
-                self.cycle += 1;
-                on_cycle(self.cycle,self.x);
-            },
-            Instruction::Addx(amount) => {
-                self.cycle += 1;
-                on_cycle(self.cycle,self.x);
-                self.cycle += 1;
-                on_cycle(self.cycle,self.x);
+            Instruction::Noop => self.pc += 1,
+            // addx takes two cycles and only changes X once both are done
+            Instruction::Addx(amount) if self.adding => {
                 self.x += amount;
+                self.adding = false;
+                self.pc += 1;
             }
+            Instruction::Addx(_) => self.adding = true,
         }
+        self.cycle += 1;
+        Some(during)
     }
 }
 
+#[derive(LineFormat)]
 enum Instruction {
+    #[line_format("noop")]
     Noop,
+    #[line_format("addx {0}")]
     Addx(i64),
 }
-impl TryFrom<&str> for Instruction {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut value = value.split(" ");
-        let op = value.next().ok_or_else(||anyhow::anyhow!("no op"))?;
-        Ok(match op {
-            "noop" => Instruction::Noop,
-            "addx" => {
-                let amount = value.next().ok_or_else(||anyhow::anyhow!("no amount"))?;
-                let amount = amount.parse::<i64>()?;
-                Instruction::Addx(amount)
-            },
-            _ => anyhow::bail!("unknown op {}",op)
-        })
+
+#[derive(Default)]
+pub struct Crt {
+    pub rows: Vec<Vec<char>>,
+}
+impl Crt {
+    pub const WIDTH: usize = 40;
+
+    /// Draws the pixel the beam is over during `cycle`, lit when the three
+    /// wide sprite centered on `x` covers it.
+    pub fn draw(&mut self, cycle: u64, x: i64) {
+        let index = (cycle - 1) as usize;
+        if index.is_multiple_of(Self::WIDTH) {
+            self.rows.push(Vec::with_capacity(Self::WIDTH));
+        }
+        let column = (index % Self::WIDTH) as i64;
+        let row = self.rows.last_mut().unwrap();
+        row.push(if (column - x).abs() <= 1 { '#' } else { '.' });
+    }
+
+    pub fn render(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
-    
 }
 
 #[allow(unused)]
-fn part1(data: &str) -> Result<String> {
-    let mut sum = RefCell::new(0i64);
-    let mut cpu = CPU::new();
+pub fn part1(data: &str) -> Result<String> {
+    let mut cpu = Cpu::new(data)?;
 
-    let check_cycles = [20,60,100,140,180,220];
+    let check_cycles = [20, 60, 100, 140, 180, 220];
 
-    for line in data.lines() {
-        let inst : Instruction = line.try_into()?;
-        cpu.eval(&inst,|cycle,x| {
-            if check_cycles.contains(&cycle) {
-                *sum.borrow_mut() += x * (cycle as i64);
-            }
-        });
+    let mut sum = 0i64;
+    while let Some((cycle, x)) = cpu.tick() {
+        if check_cycles.contains(&cycle) {
+            sum += x * (cycle as i64);
+        }
     }
-    Ok(sum.into_inner().to_string())
+    Ok(sum.to_string())
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
-    let crt = vec![vec![]];
-
-    let mut crt = RefCell::new(crt);
-    let mut cpu = CPU::new();
-
-    let check_cycles = [20,60,100,140,180,220];
-
-    for line in data.lines() {
-        let inst : Instruction = line.try_into()?;
-        cpu.eval(&inst,|cycle,x| {
-            let mut crt = crt.borrow_mut();
-            let row = crt.last_mut().unwrap();
-            let row = if row.len() >= 40 {
-                crt.push(vec![]);
-                crt.last_mut().unwrap()
-            } else {
-                row
-            };
-
-            let dist = row.len() as i64  - x;
-            let dist = dist.abs();
-            row.push(if dist <= 1 {
-                '#' 
-            } else {
-                '.'
-            });
-        });
-    }
+pub fn part2(data: &str) -> Result<String> {
+    let mut cpu = Cpu::new(data)?;
+    let mut crt = Crt::default();
 
-    for row in crt.borrow().iter() {
-        println!("{}",row.iter().collect::<String>());
+    while let Some((cycle, x)) = cpu.tick() {
+        crt.draw(cycle, x);
     }
-    Ok("".to_string())
+
+    Ok(crt.render())
+}
+
+#[test]
+fn test_cpu_tick() -> Result<()> {
+    let mut cpu = Cpu::new("noop\naddx 3\naddx -5")?;
+    let ticks = std::iter::from_fn(|| cpu.tick()).collect::<Vec<_>>();
+    assert_eq!(ticks, vec![(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+    assert_eq!(cpu.x, -1);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -115,11 +123,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
@@ -143,9 +151,17 @@ fn test_part1() -> Result<()> {
 #[test]
 #[allow(unused)]
 fn test_part2() -> Result<()> {
-    let expected = "foobar".to_string();
+    let expected = [
+        "##..##..##..##..##..##..##..##..##..##..",
+        "###...###...###...###...###...###...###.",
+        "####....####....####....####....####....",
+        "#####.....#####.....#####.....#####.....",
+        "######......######......######......####",
+        "#######.......#######.......#######.....",
+    ]
+    .join("\n");
     let got = part2(sample_data()?.as_str())?;
 
     assert_eq!(expected,got);
     Ok(())
-}
\ No newline at end of file
+}
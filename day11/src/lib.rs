@@ -1,7 +1,9 @@
 use num_traits::Zero;
-use std::{cell::RefCell, collections::VecDeque};
+use std::collections::VecDeque;
 
 use anyhow::Result;
+use expression::{Expr, Op};
+use line_format::LineFormat;
 pub mod data;
 
 type ValueType = u64;
@@ -9,7 +11,7 @@ type ValueType = u64;
 pub struct Monkey {
     pub id: usize,
     pub items: VecDeque<Item>,
-    pub operation: Box<dyn Fn(ValueType) -> ValueType>,
+    pub operation: Box<dyn Fn(ValueType) -> Result<ValueType>>,
     pub test: Box<dyn Fn(ValueType) -> bool>,
     pub divisor: ValueType,
     pub true_monkey: usize,
@@ -129,39 +131,16 @@ fn test_monkey() -> Result<()> {
     Ok(())
 }
 
+#[derive(LineFormat)]
+#[line_format("Test: divisible by {divisor}")]
+struct DivisibleTest {
+    divisor: ValueType,
+}
+
 pub fn parse_test(test: &str) -> Result<(Box<dyn Fn(ValueType) -> bool>, u64)> {
-    let mut test = test.trim().split(" ");
-
-    if test
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get Test:"))?
-        != "Test:"
-    {
-        anyhow::bail!("Expected Test:");
-    }
-    if test
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("COuld not get divisible"))?
-        != "divisible"
-    {
-        anyhow::bail!("Expected divisible");
-    }
-    if test
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get by"))?
-        != "by"
-    {
-        anyhow::bail!("Expected by");
-    }
-    let divisor: ValueType = test
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get value"))?
-        .parse()?;
+    let DivisibleTest { divisor } = test.trim().try_into()?;
 
-    let op = move |v| {
-        let divisor = divisor;
-        v % divisor == Zero::zero()
-    };
+    let op = move |v| v % divisor == Zero::zero();
 
     Ok((Box::new(op), divisor))
 }
@@ -191,71 +170,50 @@ fn test_parsetest() -> Result<()> {
     Ok(())
 }
 
-pub fn parse_operation(op: &str) -> Result<Box<dyn Fn(ValueType) -> ValueType>> {
-    let mut op = op.trim().split(" ");
-    let operation = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get operation"))?;
-    let new = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get new value"))?;
-    let equals = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get equals"))?;
-    let old = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get old value"))?;
-    let real_op = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get real operation"))?;
-    let operand = op
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not get operand"))?;
-
-    let operand: Option<u64> = match operand {
-        "old" => None,
-        _ => Some(operand.parse()?),
-    };
-
-    if operation != "Operation:" {
-        anyhow::bail!("Operation not found");
-    }
-    if new != "new" {
-        anyhow::bail!("New now found");
-    }
-    if equals != "=" {
-        anyhow::bail!("Equals not found");
-    }
-    if old != "old" {
-        anyhow::bail!("Old not found");
+/// Whether `expr` only adds and multiplies `old` and numbers, which is all
+/// that survives working modulo the monkeys' divisors.
+fn is_modular(expr: &Expr) -> bool {
+    match expr {
+        Expr::Num(n) => *n >= 0,
+        Expr::Var(name) => name == "old",
+        Expr::Binary(left, op, right) => {
+            matches!(op, Op::Add | Op::Mul) && is_modular(left) && is_modular(right)
+        }
     }
+}
 
-    let op: Box<dyn Fn(ValueType) -> ValueType> = match real_op {
-        "*" => Box::new(move |value| {
-            if let Some(o) = operand {
-                value * o
-            } else {
-                value.clone() * value
-            }
-        }),
-        "+" => Box::new(move |value| {
-            if let Some(o) = operand {
-                value + o
-            } else {
-                value.clone() + value
-            }
-        }),
-        _ => anyhow::bail!("Unknown operation {real_op}"),
-    };
+pub fn parse_operation(op: &str) -> Result<Box<dyn Fn(ValueType) -> Result<ValueType>>> {
+    let text = op
+        .trim()
+        .strip_prefix("Operation: new = ")
+        .ok_or_else(|| anyhow::anyhow!("Expected `Operation: new = ...` but got `{op}`"))?;
+    let expr = Expr::parse(text)?;
+    if !is_modular(&expr) {
+        anyhow::bail!("Only + and * of old and numbers are supported, not `{text}`");
+    }
 
-    Ok(op)
+    Ok(Box::new(move |value| {
+        let new = expr.eval(&|name| (name == "old").then_some(i128::from(value)))?;
+        ValueType::try_from(new).map_err(|_| anyhow::anyhow!("{expr} is too big for old = {value}"))
+    }))
 }
 
 #[test]
 fn test_op() -> Result<()> {
     let op = parse_operation("  Operation: new = old * 19")?;
 
-    assert_eq!((19 * 3), op(3));
+    assert_eq!((19 * 3), op(3)?);
+
+    let op = parse_operation("  Operation: new = old + old")?;
+    assert_eq!(6, op(3)?);
+
+    let op = parse_operation("  Operation: new = (old + 2) * old")?;
+    assert_eq!(15, op(3)?);
+    assert!(op(ValueType::MAX).is_err());
+
+    assert!(parse_operation("  Operation: new = old - 3").is_err());
+    assert!(parse_operation("  Operation: new = old / 3").is_err());
+    assert!(parse_operation("  Operation: new = older * 3").is_err());
 
     Ok(())
 }
@@ -263,7 +221,6 @@ fn test_op() -> Result<()> {
 #[derive(Debug)]
 pub struct Item {
     pub value: ValueType,
-    pub inspection_list: Vec<usize>,
 }
 impl TryFrom<&str> for Item {
     type Error = anyhow::Error;
@@ -273,93 +230,119 @@ impl TryFrom<&str> for Item {
             value: s
                 .parse()
                 .map_err(|e| anyhow::anyhow!("Couldn't parse item from {s}: {e}"))?,
-            inspection_list: Vec::new(),
         })
     }
 }
 
-pub fn part1(data: &str) -> Result<String> {
-    Ok(solver(data, 20, 3)?.to_string())
+/// An item passed from one monkey to another.
+#[derive(Debug, Clone, Copy)]
+pub struct Throw {
+    pub from: usize,
+    pub to: usize,
+    pub value: ValueType,
 }
 
-fn solver(data: &str, rounds: usize, worry: ValueType) -> Result<usize> {
-    let monkeys = monkeys(data)?;
-    // turn monkeys into refcells
-    let monkeys: Vec<_> = monkeys.into_iter().map(|m| RefCell::new(m)).collect();
-
-    // compute the multiple of all divisors.  This will keep our values in check (to a point)
-    let value_modulus: ValueType = monkeys.iter().map(|m| m.borrow().divisor).product();
-
-    for _round in 0..rounds {
-        // We have to do index based array manipulation because of borrowing
-        for i in 0..monkeys.len() {
-            println!("Monkey {i}:");
-            let mut m = monkeys
-                .get(i)
-                .ok_or_else(|| anyhow::anyhow!("deverr"))?
-                .borrow_mut();
-
-            while let Some(mut item) = m.items.pop_front() {
-                println!(
-                    "  Monkey inspects an item with a worry level of {}.",
-                    item.value
-                );
-                item.inspection_list.push(i);
-                let newvalue = (m.operation)(item.value);
-                println!("  newvalue: {newvalue}");
-                let newvalue = newvalue / worry.clone();
-                let newvalue = newvalue % value_modulus;
-                println!("  bored: {newvalue}");
-                let mut newmonkey = if (m.test)(newvalue.clone()) {
-                    println!("  true: give to {}", m.true_monkey);
-                    monkeys
-                        .get(m.true_monkey)
-                        .ok_or_else(|| anyhow::anyhow!("COuld not get true monkey"))?
+/// The game of keep away, played one throw at a time.
+pub struct KeepAway {
+    pub monkeys: Vec<Monkey>,
+    /// How many items each monkey has inspected so far
+    pub inspections: Vec<usize>,
+    pub round: usize,
+    /// The monkey whose turn it is
+    pub turn: usize,
+    rounds: usize,
+    worry: ValueType,
+    value_modulus: ValueType,
+}
+impl KeepAway {
+    pub fn new(data: &str, rounds: usize, worry: ValueType) -> Result<Self> {
+        let monkeys = monkeys(data)?;
+        if monkeys.is_empty() {
+            anyhow::bail!("No monkeys to play keep away");
+        }
+        // compute the multiple of all divisors.  This will keep our values in check (to a point)
+        let value_modulus = monkeys.iter().map(|m| m.divisor).product();
+        Ok(Self {
+            inspections: vec![0; monkeys.len()],
+            monkeys,
+            round: 0,
+            turn: 0,
+            rounds,
+            worry,
+            value_modulus,
+        })
+    }
+
+    /// The monkey whose turn it is inspects and throws its next item.  Turns
+    /// with nothing left to throw are skipped.  Returns `None` once all the
+    /// rounds have been played.
+    pub fn step(&mut self) -> Result<Option<Throw>> {
+        while self.round < self.rounds {
+            let m = &mut self.monkeys[self.turn];
+            if let Some(mut item) = m.items.pop_front() {
+                self.inspections[self.turn] += 1;
+                let newvalue = (m.operation)(item.value)? / self.worry % self.value_modulus;
+                let to = if (m.test)(newvalue) {
+                    m.true_monkey
                 } else {
-                    println!("  false: give to {}", m.false_monkey);
-                    monkeys
-                        .get(m.false_monkey)
-                        .ok_or_else(|| anyhow::anyhow!("COuld not get false monkey"))?
-                }
-                .borrow_mut();
+                    m.false_monkey
+                };
                 item.value = newvalue;
-                newmonkey.items.push_back(item);
+                self.monkeys
+                    .get_mut(to)
+                    .ok_or_else(|| anyhow::anyhow!("Could not get monkey {to}"))?
+                    .items
+                    .push_back(item);
+                return Ok(Some(Throw {
+                    from: self.turn,
+                    to,
+                    value: newvalue,
+                }));
+            }
+            self.turn += 1;
+            if self.turn == self.monkeys.len() {
+                self.turn = 0;
+                self.round += 1;
             }
         }
+        Ok(None)
     }
 
-    let mut counts = (0..monkeys.len())
-        .into_iter()
-        .map(|monkey_index| {
-            monkeys
-                .iter()
-                .flat_map(|m| {
-                    m.borrow()
-                        .items
-                        .iter()
-                        .flat_map(|i| i.inspection_list.clone())
-                        .collect::<Vec<_>>()
-                })
-                .filter(|i| *i == monkey_index)
-                .count()
-        })
-        .enumerate()
-        .collect::<Vec<_>>();
-
-    counts.sort_by(|a, b| b.1.cmp(&a.1));
-    println!("{counts:?}");
-
-    let products = counts
-        .get(0)
-        .ok_or_else(|| anyhow::anyhow!("COuldn't get first count"))?
-        .1
-        * counts
-            .get(1)
-            .ok_or_else(|| anyhow::anyhow!("Could not get second count"))?
-            .1;
-    //println!("{monkeys:?}");
-
-    Ok(products)
+    pub fn run(&mut self) -> Result<()> {
+        while self.step()?.is_some() {}
+        Ok(())
+    }
+
+    /// The inspection counts of the two busiest monkeys multiplied together.
+    pub fn monkey_business(&self) -> Result<usize> {
+        let mut counts = self.inspections.clone();
+        counts.sort_by(|a, b| b.cmp(a));
+        match counts[..] {
+            [first, second, ..] => Ok(first * second),
+            _ => anyhow::bail!("Need at least two monkeys"),
+        }
+    }
+}
+
+#[test]
+fn test_keep_away_step() -> Result<()> {
+    let mut game = KeepAway::new(&data::sample_data()?, 1, 3)?;
+    let throw = game.step()?.expect("first throw");
+    assert_eq!((throw.from, throw.to, throw.value), (0, 3, 500));
+    game.run()?;
+    assert_eq!(game.inspections, vec![2, 4, 3, 5]);
+    assert_eq!(game.round, 1);
+    Ok(())
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(solver(data, 20, 3)?.to_string())
+}
+
+fn solver(data: &str, rounds: usize, worry: ValueType) -> Result<usize> {
+    let mut game = KeepAway::new(data, rounds, worry)?;
+    game.run()?;
+    game.monkey_business()
 }
 
 pub fn part2(data: &str) -> Result<String> {
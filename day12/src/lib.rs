@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use anyhow::Result;
+use geometry::Point;
 use petgraph::graph::DiGraph;
 pub mod data;
 
@@ -15,11 +16,11 @@ fn parse(data: &str) -> Result<(petgraph::Graph<i32, ()>, u32, u32, Vec<u32>)> {
         for ch in line.chars() {
             let ch = match ch {
                 'S' => {
-                    source = Some((grid.len(), row.len()));
+                    source = Some(Point::new(row.len(), grid.len()));
                     'a'
                 }
                 'E' => {
-                    dest = Some((grid.len(), row.len()));
+                    dest = Some(Point::new(row.len(), grid.len()));
                     'z'
                 }
                 _ => ch,
@@ -32,66 +33,51 @@ fn parse(data: &str) -> Result<(petgraph::Graph<i32, ()>, u32, u32, Vec<u32>)> {
         grid.push(row);
     }
 
+    let row_len = grid.first().map(|row| row.len()).unwrap_or(0);
+    let rows = grid.len();
+
     // create all edges
     let mut edges = Vec::new();
-    for y in 0..grid.len() {
-        for x in 0..grid.first().map(|r| r.len()).unwrap_or(0) {
-            let my_grid = (y, x);
-            let my_grid_i32: (i32, i32) = (y.try_into()?, x.try_into()?);
+    for y in 0..rows {
+        for x in 0..row_len {
+            let me = Point::new(x, y);
             let my_height = grid[y][x];
 
-            let mut test_dir = |off: (i32, i32)| -> Result<()> {
-                let y: i32 = off.0 + my_grid_i32.0;
-                let x: i32 = off.1 + my_grid_i32.1;
-                // Intentionally fail this, if we cannot convert,
-                let y: usize = y.try_into()?;
-                let x: usize = x.try_into()?;
-
-                let their_height = grid
-                    .get(y)
-                    .and_then(|row| row.get(x))
-                    .ok_or_else(|| anyhow::anyhow!("Grid not accessible"))?;
-                if *their_height <= my_height + 1 {
-                    edges.push((my_grid, (y, x)));
+            // Neighbours off the top or left edge are never produced, and the
+            // bounds check drops the ones off the bottom or right.
+            for them in me.neighbours4().filter(|p| p.in_bounds(row_len, rows)) {
+                if grid[them.y][them.x] <= my_height + 1 {
+                    edges.push((me, them));
                 }
-                Ok(())
-            };
-
-            // We explicitly ignore the result because the err result indicates
-            // an out of bounds attempt;
-            _ = test_dir((-1, 0));
-            _ = test_dir((1, 0));
-            _ = test_dir((0, -1));
-            _ = test_dir((0, 1));
+            }
         }
     }
 
     //println!("{grid:?}");
     //println!("{edges:?}");
 
-    let row_len = grid.first().map(|row| row.len()).unwrap_or(0);
-    let to_unwound = |(y, x): (usize, usize)| -> u32 { (y * row_len + x).try_into().unwrap() };
+    let to_unwound = |p: Point<usize>| -> Result<u32> { Ok(p.index(row_len).try_into()?) };
 
     // Convert edges to a linear space
     let edges = edges
         .into_iter()
-        .map(|(from, to)| (to_unwound(from), to_unwound(to)))
-        .collect::<Vec<_>>();
+        .map(|(from, to)| Ok((to_unwound(from)?, to_unwound(to)?)))
+        .collect::<Result<Vec<_>>>()?;
 
     let graph = petgraph::Graph::<i32, ()>::from_edges(&edges);
 
     // find all the start points
     let startpoints = grid
         .into_iter()
-        .flat_map(|row| row)
+        .flatten()
         .enumerate()
         .filter(|(_, height)| *height == 0)
         .map(|(i, _)| i as u32);
 
     Ok((
         graph,
-        to_unwound(source.unwrap()),
-        to_unwound(dest.unwrap()),
+        to_unwound(source.ok_or_else(|| anyhow::anyhow!("No start"))?)?,
+        to_unwound(dest.ok_or_else(|| anyhow::anyhow!("No end"))?)?,
         startpoints.collect(),
     ))
 }
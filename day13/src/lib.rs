@@ -0,0 +1,228 @@
+use std::{cmp::Ordering, fmt};
+
+use anyhow::Result;
+pub mod data;
+
+/// A packet from the distress signal, an integer or a list of packets.
+///
+/// Equality follows the ordering, so `[[1]]` equals `[1]`.
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b),
+            // A lone integer compares as a list holding just that integer
+            (Packet::Int(_), Packet::List(b)) => std::slice::from_ref(self).cmp(b),
+            (Packet::List(a), Packet::Int(_)) => a.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Int(value) => write!(f, "{value}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl TryFrom<&str> for Packet {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let mut parser = Parser {
+            input: value,
+            pos: 0,
+        };
+        let packet = parser.list()?;
+        if parser.pos < value.len() {
+            return Err(parser.error("end of packet"));
+        }
+        Ok(packet)
+    }
+}
+
+impl std::str::FromStr for Packet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+
+/// Recursive descent over the nested list syntax, `[1,[2,3],[]]`.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8, what: &str) -> Result<()> {
+        if self.peek() != Some(byte) {
+            return Err(self.error(what));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn packet(&mut self) -> Result<Packet> {
+        match self.peek() {
+            Some(b'[') => self.list(),
+            Some(b'0'..=b'9') => self.int(),
+            _ => Err(self.error("`[` or a number")),
+        }
+    }
+
+    fn list(&mut self) -> Result<Packet> {
+        self.expect(b'[', "`[`")?;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Packet::List(items));
+        }
+        loop {
+            items.push(self.packet()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Packet::List(items));
+                }
+                _ => return Err(self.error("`,` or `]`")),
+            }
+        }
+    }
+
+    fn int(&mut self) -> Result<Packet> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits = &self.input[start..self.pos];
+        digits.parse().map(Packet::Int).map_err(|e| {
+            anyhow::anyhow!(
+                "could not parse `{digits}` at column {} of `{}`: {e}",
+                start + 1,
+                self.input
+            )
+        })
+    }
+
+    fn error(&self, expected: &str) -> anyhow::Error {
+        let found = match self.input[self.pos..].chars().next() {
+            Some(c) => format!("`{c}`"),
+            None => "the end".to_string(),
+        };
+        anyhow::anyhow!(
+            "expected {expected} but found {found} at column {} of `{}`",
+            self.pos + 1,
+            self.input
+        )
+    }
+}
+
+/// Every packet in the input, skipping the blank lines between pairs.
+pub fn parse(data: &str) -> Result<Vec<Packet>> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Packet::try_from(line.trim()).map_err(|e| anyhow::anyhow!("line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let packets = parse(data)?;
+    if packets.len() % 2 != 0 {
+        anyhow::bail!("Packets should come in pairs, found {}", packets.len());
+    }
+    let sum: usize = packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] <= pair[1])
+        .map(|(i, _)| i + 1)
+        .sum();
+    Ok(sum.to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let mut packets = parse(data)?;
+    let dividers: [Packet; 2] = ["[[2]]".try_into()?, "[[6]]".try_into()?];
+    packets.extend(dividers.iter().cloned());
+    packets.sort();
+
+    // Packets equal to a divider, like `[2]`, count as after it
+    let key: usize = dividers
+        .iter()
+        .map(|d| packets.partition_point(|p| p < d) + 1)
+        .product();
+    Ok(key.to_string())
+}
+
+#[test]
+fn test_parse() -> Result<()> {
+    let packet: Packet = "[1,[2,[]],10]".parse()?;
+    assert_eq!(
+        packet,
+        Packet::List(vec![
+            Packet::Int(1),
+            Packet::List(vec![Packet::Int(2), Packet::List(vec![])]),
+            Packet::Int(10),
+        ])
+    );
+    assert_eq!(packet.to_string(), "[1,[2,[]],10]");
+
+    let err = Packet::try_from("[1,2").unwrap_err().to_string();
+    assert_eq!(err, "expected `,` or `]` but found the end at column 5 of `[1,2`");
+    let err = Packet::try_from("[1,,2]").unwrap_err().to_string();
+    assert_eq!(err, "expected `[` or a number but found `,` at column 4 of `[1,,2]`");
+    assert!(Packet::try_from("[1]]").is_err());
+    assert!(Packet::try_from("1").is_err());
+    assert!(parse("[1]\n\n[x]").unwrap_err().to_string().starts_with("line 3: "));
+    Ok(())
+}
+
+#[test]
+fn test_order() -> Result<()> {
+    let p = |s: &str| Packet::try_from(s).unwrap();
+    assert!(p("[1,1,3,1,1]") < p("[1,1,5,1,1]"));
+    assert!(p("[[1],[2,3,4]]") < p("[[1],4]"));
+    assert!(p("[9]") > p("[[8,7,6]]"));
+    assert!(p("[[[]]]") > p("[[]]"));
+    assert_eq!(p("[[1]]"), p("[1]"));
+    Ok(())
+}
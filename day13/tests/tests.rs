@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "13".to_string();
+    let got = aocday13::part1(aocday13::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "140".to_string();
+    let got = aocday13::part2(aocday13::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,261 @@
+use anyhow::Result;
+use geometry::{Point, Vector};
+pub mod data;
+
+pub const SOURCE: Point<i64> = Point::new(500, 0);
+
+/// The most cells a cave may take, a little over a hundred megabytes and far
+/// more than any puzzle needs.
+pub const MAX_CELLS: i64 = 1 << 27;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// One `x,y -> x,y -> ...` line of rock.
+pub fn parse_path(line: &str) -> Result<Vec<Point<i64>>> {
+    line.split(" -> ")
+        .map(|point| {
+            let (x, y) = point
+                .trim()
+                .split_once(',')
+                .ok_or_else(|| anyhow::anyhow!("Expected x,y but got `{point}` in `{line}`"))?;
+            Ok(Point::new(
+                x.parse()
+                    .map_err(|e| anyhow::anyhow!("Bad x `{x}` in `{line}`: {e}"))?,
+                y.parse()
+                    .map_err(|e| anyhow::anyhow!("Bad y `{y}` in `{line}`: {e}"))?,
+            ))
+        })
+        .collect()
+}
+
+/// The cave as a dense grid just wide enough for where sand can reach.
+///
+/// Sand spreads at most one column per row, so it can never leave the
+/// triangle under the source and the grid only needs to be twice as wide as
+/// the cave is deep, however far the rock paths stretch.  It still grows
+/// with the square of the depth, so a cave deeper than [`MAX_CELLS`] allows
+/// is an error rather than an allocation that cannot succeed.
+pub struct Cave {
+    cells: Vec<Cell>,
+    left: i64,
+    width: i64,
+    height: i64,
+    /// The lowest rock
+    bottom: i64,
+    /// Rows at and below this are solid, when there is a floor
+    floor: Option<i64>,
+    /// Where the last grain went, so the next one starts from there
+    path: Vec<Point<i64>>,
+    pub grains: usize,
+}
+impl Cave {
+    pub fn new(data: &str, floor: bool) -> Result<Self> {
+        let paths = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_path)
+            .collect::<Result<Vec<_>>>()?;
+        let rocks = paths.iter().flatten();
+        let bottom = rocks.clone().map(|p| p.y).max().unwrap_or(0);
+        if let Some(p) = rocks.clone().find(|p| p.y < 0) {
+            anyhow::bail!("Rock at {},{} is above the source", p.x, p.y);
+        }
+        let floor = floor.then_some(bottom.saturating_add(2));
+
+        let height = floor.unwrap_or(bottom.saturating_add(1));
+        let cells = height
+            .checked_mul(2)
+            .and_then(|width| width.checked_add(1))
+            .and_then(|width| width.checked_mul(height));
+        if cells.is_none_or(|cells| cells > MAX_CELLS) {
+            anyhow::bail!(
+                "Rock down to a depth of {bottom} needs a grid bigger than {MAX_CELLS} cells"
+            );
+        }
+        let left = SOURCE.x - height;
+        let width = 2 * height + 1;
+        let mut cave = Self {
+            cells: vec![Cell::Air; (width * height) as usize],
+            left,
+            width,
+            height,
+            bottom,
+            floor,
+            path: Vec::new(),
+            grains: 0,
+        };
+
+        for path in paths {
+            for pair in path.windows(2) {
+                let step = (pair[1] - pair[0]).signum();
+                if step.x != 0 && step.y != 0 {
+                    anyhow::bail!(
+                        "Rock from {},{} to {},{} is not straight",
+                        pair[0].x,
+                        pair[0].y,
+                        pair[1].x,
+                        pair[1].y
+                    );
+                }
+                // Only the part of the segment inside the grid matters
+                let xs = pair[0].x.min(pair[1].x).max(cave.left)
+                    ..=pair[0].x.max(pair[1].x).min(cave.left + cave.width - 1);
+                for x in xs {
+                    for y in pair[0].y.min(pair[1].y)..=pair[0].y.max(pair[1].y) {
+                        cave.set(Point::new(x, y), Cell::Rock);
+                    }
+                }
+            }
+            if let [p] = path[..] {
+                cave.set(p, Cell::Rock);
+            }
+        }
+        Ok(cave)
+    }
+
+    fn index(&self, p: Point<i64>) -> Option<usize> {
+        let x = p.x - self.left;
+        (x >= 0 && x < self.width && p.y >= 0 && p.y < self.height)
+            .then(|| (p.y * self.width + x) as usize)
+    }
+
+    pub fn get(&self, p: Point<i64>) -> Cell {
+        if self.floor.is_some_and(|floor| p.y >= floor) {
+            return Cell::Rock;
+        }
+        self.index(p).map(|i| self.cells[i]).unwrap_or(Cell::Air)
+    }
+
+    fn set(&mut self, p: Point<i64>, cell: Cell) {
+        if let Some(i) = self.index(p) {
+            self.cells[i] = cell;
+        }
+    }
+
+    /// Drops one grain, returning where it came to rest, or `None` once sand
+    /// falls into the abyss or the source is blocked.
+    pub fn drop_grain(&mut self) -> Option<Point<i64>> {
+        if self.path.is_empty() {
+            if self.get(SOURCE) != Cell::Air {
+                return None;
+            }
+            self.path.push(SOURCE);
+        }
+        loop {
+            let p = *self.path.last()?;
+            if self.floor.is_none() && p.y >= self.bottom {
+                return None;
+            }
+            let below = [Vector::new(0, 1), Vector::new(-1, 1), Vector::new(1, 1)]
+                .into_iter()
+                .map(|v| p + v)
+                .find(|&next| self.get(next) == Cell::Air);
+            match below {
+                Some(next) => self.path.push(next),
+                None => {
+                    self.path.pop();
+                    self.set(p, Cell::Sand);
+                    self.grains += 1;
+                    return Some(p);
+                }
+            }
+        }
+    }
+
+    /// Drops grains until one is lost or the source is blocked.
+    pub fn fill(&mut self) -> usize {
+        while self.drop_grain().is_some() {}
+        self.grains
+    }
+
+    /// The cave cropped to the rock and sand, with the floor if there is one.
+    pub fn render(&self) -> String {
+        let occupied = (0..self.cells.len() as i64)
+            .filter(|&i| self.cells[i as usize] != Cell::Air)
+            .map(|i| i % self.width + self.left);
+        let left = occupied.clone().min().unwrap_or(SOURCE.x).min(SOURCE.x);
+        let right = occupied.max().unwrap_or(SOURCE.x).max(SOURCE.x);
+        let bottom = self.floor.unwrap_or(self.bottom);
+
+        let mut out = String::new();
+        for y in 0..=bottom {
+            for x in left..=right {
+                let p = Point::new(x, y);
+                out.push(match self.get(p) {
+                    Cell::Rock => '#',
+                    Cell::Sand => 'o',
+                    Cell::Air if p == SOURCE => '+',
+                    Cell::Air => '.',
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(Cave::new(data, false)?.fill().to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    Ok(Cave::new(data, true)?.fill().to_string())
+}
+
+#[test]
+fn test_render() -> Result<()> {
+    let mut cave = Cave::new(include_str!("../sample.txt"), false)?;
+    cave.fill();
+    let expected = "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+";
+    assert_eq!(cave.render(), expected);
+    Ok(())
+}
+
+#[test]
+fn test_floor() -> Result<()> {
+    let mut cave = Cave::new(include_str!("../sample.txt"), true)?;
+    assert_eq!(cave.fill(), 93);
+    assert_eq!(cave.get(SOURCE), Cell::Sand);
+    assert_eq!(cave.drop_grain(), None);
+    let render = cave.render();
+    assert!(render.starts_with("..........o.........."));
+    assert!(render.ends_with("#####################\n"));
+
+    // Rock far off to the side, where no sand can reach, takes no space
+    let cave = Cave::new("500,5 -> 1000000000,5", true)?;
+    assert_eq!(cave.cells.len(), 15 * 7);
+
+    // Rock too deep to fit a grid around is an error, not an abort
+    for floor in [false, true] {
+        let err = Cave::new("500,1000000", floor).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!("Rock down to a depth of 1000000 needs a grid bigger than {MAX_CELLS} cells")
+        );
+        assert!(Cave::new(&format!("500,{}", i64::MAX), floor).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Cave::new("498,4 -> 500,6", false).is_err());
+    assert!(Cave::new("498,4 -> 500", false).is_err());
+    assert!(Cave::new("498,x", false).is_err());
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "24".to_string();
+    let got = aocday14::part1(aocday14::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "93".to_string();
+    let got = aocday14::part2(aocday14::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
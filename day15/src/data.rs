@@ -0,0 +1,13 @@
+use anyhow::Result;
+
+pub fn sample_data() -> Result<String> {
+    Ok(include_str!("../sample.txt").to_string())
+}
+
+pub fn part1_problem_data() -> Result<String> {
+    puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))
+}
+
+pub fn part2_problem_data() -> Result<String> {
+    puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))
+}
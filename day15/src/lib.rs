@@ -0,0 +1,173 @@
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use anyhow::Result;
+use geometry::Point;
+use line_format::LineFormat;
+pub mod data;
+
+/// The row part 1 looks at in the real input, the sample uses 10.
+pub const ROW: i64 = 2_000_000;
+/// The largest coordinate of the distress beacon in the real input, the
+/// sample uses 20.
+pub const BOUND: i64 = 4_000_000;
+
+#[derive(Clone, Debug, LineFormat)]
+#[line_format("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}")]
+pub struct Sensor {
+    pub sx: i64,
+    pub sy: i64,
+    pub bx: i64,
+    pub by: i64,
+}
+impl Sensor {
+    pub fn pos(&self) -> Point<i64> {
+        Point::new(self.sx, self.sy)
+    }
+
+    pub fn beacon(&self) -> Point<i64> {
+        Point::new(self.bx, self.by)
+    }
+
+    /// Nothing closer than the beacon can be another beacon.
+    pub fn radius(&self) -> i64 {
+        self.pos().manhattan(self.beacon())
+    }
+
+    pub fn covers(&self, p: Point<i64>) -> bool {
+        self.pos().manhattan(p) <= self.radius()
+    }
+
+    /// The columns of `row` inside the sensor's radius.
+    pub fn on_row(&self, row: i64) -> Option<RangeInclusive<i64>> {
+        let spare = self.radius() - (row - self.sy).abs();
+        (spare >= 0).then(|| self.sx - spare..=self.sx + spare)
+    }
+}
+
+pub fn parse(data: &str) -> Result<Vec<Sensor>> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.try_into())
+        .collect()
+}
+
+/// Sorts and joins overlapping or touching ranges.
+pub fn merge(mut ranges: Vec<RangeInclusive<i64>>) -> Vec<RangeInclusive<i64>> {
+    ranges.sort_by_key(|r| *r.start());
+    let mut merged: Vec<RangeInclusive<i64>> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if *r.start() <= *last.end() + 1 => {
+                if r.end() > last.end() {
+                    *last = *last.start()..=*r.end();
+                }
+            }
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Merged coverage of every sensor on `row`.
+pub fn coverage(sensors: &[Sensor], row: i64) -> Vec<RangeInclusive<i64>> {
+    merge(sensors.iter().filter_map(|s| s.on_row(row)).collect())
+}
+
+/// Positions on `row` that cannot hold a beacon.
+pub fn excluded(sensors: &[Sensor], row: i64) -> i64 {
+    let ranges = coverage(sensors, row);
+    let covered: i64 = ranges.iter().map(|r| r.end() - r.start() + 1).sum();
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon())
+        .filter(|b| b.y == row && ranges.iter().any(|r| r.contains(&b.x)))
+        .collect::<HashSet<_>>()
+        .len() as i64;
+    covered - beacons
+}
+
+/// The one position in `0..=bound` squared that no sensor covers.
+///
+/// If there is exactly one, it sits just outside the radius of several
+/// sensors, so it is where two of their borders cross.  Borders run along
+/// the diagonals `x + y = a` and `x - y = b`, which makes every crossing
+/// cheap to list.  A spot in a corner of the area can be boxed in by the
+/// edges instead, so the per row sweep covers that case.
+pub fn distress_beacon(sensors: &[Sensor], bound: i64) -> Option<Point<i64>> {
+    let area = |p: Point<i64>| p.in_bounds(bound + 1, bound + 1);
+    let free = |p: Point<i64>| area(p) && !sensors.iter().any(|s| s.covers(p));
+
+    let mut rising = HashSet::new();
+    let mut falling = HashSet::new();
+    for s in sensors {
+        let r = s.radius() + 1;
+        rising.extend([s.sx + s.sy - r, s.sx + s.sy + r]);
+        falling.extend([s.sx - s.sy - r, s.sx - s.sy + r]);
+    }
+    let crossing = rising
+        .iter()
+        .flat_map(|a| falling.iter().map(move |b| (a, b)))
+        .filter(|(a, b)| (*a - *b) % 2 == 0)
+        .map(|(a, b)| Point::new((a + b) / 2, (a - b) / 2))
+        .find(|&p| free(p));
+    crossing.or_else(|| {
+        (0..=bound).find_map(|y| {
+            let mut x = 0;
+            for r in coverage(sensors, y) {
+                if *r.start() > x {
+                    break;
+                }
+                x = x.max(r.end() + 1);
+            }
+            (x <= bound).then_some(Point::new(x, y))
+        })
+    })
+}
+
+pub fn tuning_frequency(p: Point<i64>) -> i64 {
+    p.x * 4_000_000 + p.y
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    part1_at(data, ROW)
+}
+
+pub fn part1_at(data: &str, row: i64) -> Result<String> {
+    Ok(excluded(&parse(data)?, row).to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    part2_within(data, BOUND)
+}
+
+pub fn part2_within(data: &str, bound: i64) -> Result<String> {
+    let beacon = distress_beacon(&parse(data)?, bound)
+        .ok_or_else(|| anyhow::anyhow!("Every position up to {bound} is covered"))?;
+    Ok(tuning_frequency(beacon).to_string())
+}
+
+#[test]
+fn test_merge() {
+    assert_eq!(merge(vec![6..=8, 1..=3, 2..=4]), vec![1..=4, 6..=8]);
+    assert_eq!(merge(vec![5..=8, 1..=4]), vec![1..=8]);
+    assert_eq!(merge(vec![1..=10, 2..=3]), vec![1..=10]);
+    assert_eq!(merge(vec![1..=2, 4..=5]), vec![1..=2, 4..=5]);
+}
+
+#[test]
+fn test_sensor() -> Result<()> {
+    let sensor: Sensor = "Sensor at x=8, y=7: closest beacon is at x=2, y=10".try_into()?;
+    assert_eq!(sensor.radius(), 9);
+    assert_eq!(sensor.on_row(10), Some(2..=14));
+    assert_eq!(sensor.on_row(16), Some(8..=8));
+    assert_eq!(sensor.on_row(17), None);
+    Ok(())
+}
+
+#[test]
+fn test_corner() -> Result<()> {
+    // One sensor covering all but the bottom right corner of 0..=4
+    let sensor: Sensor = "Sensor at x=0, y=0: closest beacon is at x=0, y=7".try_into()?;
+    assert_eq!(distress_beacon(&[sensor], 4), Some(Point::new(4, 4)));
+    Ok(())
+}
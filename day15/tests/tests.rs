@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "26".to_string();
+    let got = aocday15::part1_at(aocday15::data::sample_data()?.as_str(), 10)?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "56000011".to_string();
+    let got = aocday15::part2_within(aocday15::data::sample_data()?.as_str(), 20)?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use line_format::LineFormat;
+use petgraph::graph::UnGraph;
+pub mod data;
+
+pub const START: &str = "AA";
+
+#[derive(Clone, Debug, LineFormat)]
+#[line_format("Valve {name} has flow rate={rate}; {tunnels}")]
+pub struct Valve {
+    pub name: String,
+    pub rate: u32,
+    #[line_format(with = tunnels)]
+    pub tunnels: Vec<String>,
+}
+
+fn tunnels(value: &str) -> Result<Vec<String>> {
+    let list = value
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| value.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(|| anyhow::anyhow!("Expected the tunnels but got `{value}`"))?;
+    Ok(list.split(", ").map(str::to_string).collect())
+}
+
+/// The valves worth opening, and how far apart they are.
+///
+/// Valves with no flow only matter as places to walk through, so they are
+/// folded into the distances and the search only ever moves from one useful
+/// valve to the next.
+#[derive(Debug)]
+pub struct Network {
+    /// Names of the useful valves, then the start
+    pub names: Vec<String>,
+    pub rates: Vec<u32>,
+    /// Minutes to walk between any two of `names`, `u32::MAX` if no route
+    pub distances: Vec<Vec<u32>>,
+}
+impl Network {
+    pub fn new(data: &str) -> Result<Self> {
+        let valves = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Valve::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: HashMap<&str, _> = valves
+            .iter()
+            .map(|v| (v.name.as_str(), graph.add_node(())))
+            .collect();
+        for valve in &valves {
+            for tunnel in &valve.tunnels {
+                let to = nodes.get(tunnel.as_str()).ok_or_else(|| {
+                    anyhow::anyhow!("{} leads to unknown valve {tunnel}", valve.name)
+                })?;
+                graph.update_edge(nodes[valve.name.as_str()], *to, ());
+            }
+        }
+        let all_pairs = petgraph::algo::floyd_warshall(&graph, |_| 1u32)
+            .map_err(|_| anyhow::anyhow!("Tunnels cannot have negative length"))?;
+
+        let start = valves
+            .iter()
+            .find(|v| v.name == START)
+            .ok_or_else(|| anyhow::anyhow!("There is no valve {START}"))?;
+        let mut useful: Vec<&Valve> = valves.iter().filter(|v| v.rate > 0).collect();
+        if useful.len() > 20 {
+            anyhow::bail!("{} valves with flow is too many to search", useful.len());
+        }
+        useful.push(start);
+
+        let distances = useful
+            .iter()
+            .map(|from| {
+                useful
+                    .iter()
+                    .map(|to| {
+                        let key = (nodes[from.name.as_str()], nodes[to.name.as_str()]);
+                        all_pairs.get(&key).copied().unwrap_or(u32::MAX)
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            names: useful.iter().map(|v| v.name.clone()).collect(),
+            rates: useful.iter().map(|v| v.rate).collect(),
+            distances,
+        })
+    }
+
+    /// Number of valves worth opening.
+    pub fn useful(&self) -> usize {
+        self.rates.len() - 1
+    }
+
+    /// The most pressure that can be released in `time` minutes for every
+    /// set of opened valves, indexed by a bitmask of `names`.
+    ///
+    /// A state is where the worker stands, the minutes left and the valves
+    /// opened, and different orders of the same valves often meet in one.
+    /// Every move takes at least a minute, so taking the states a minute at
+    /// a time from the start means each is only expanded once, with the most
+    /// released by any way of reaching it.  The work grows with the number
+    /// of states rather than the number of orders.
+    pub fn best_by_set(&self, time: u32) -> Vec<u32> {
+        let mut best = vec![0; 1 << self.useful()];
+        // by_time[t] maps (valve, opened) with t minutes left to the most
+        // released on arriving there
+        let mut by_time: Vec<HashMap<(usize, usize), u32>> =
+            vec![HashMap::new(); time as usize + 1];
+        by_time[time as usize].insert((self.useful(), 0), 0);
+        for t in (1..=time).rev() {
+            for ((at, opened), released) in std::mem::take(&mut by_time[t as usize]) {
+                best[opened] = best[opened].max(released);
+                for next in 0..self.useful() {
+                    if opened & (1 << next) != 0 {
+                        continue;
+                    }
+                    // Walking there and a minute to open it
+                    let cost = self.distances[at][next].saturating_add(1);
+                    if cost < t {
+                        let left = t - cost;
+                        let released = released + self.rates[next] * left;
+                        let state = (next, opened | (1 << next));
+                        let entry = by_time[left as usize].entry(state).or_default();
+                        *entry = (*entry).max(released);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    pub fn alone(&self, time: u32) -> u32 {
+        self.best_by_set(time).into_iter().max().unwrap_or(0)
+    }
+
+    /// Splitting the valves between two workers who never open the same one.
+    pub fn with_elephant(&self, time: u32) -> u32 {
+        let best = self.best_by_set(time);
+        // within[set] is the best of any subset of set
+        let mut within = best.clone();
+        for bit in 0..self.useful() {
+            for set in 0..within.len() {
+                if set & (1 << bit) != 0 {
+                    within[set] = within[set].max(within[set ^ (1 << bit)]);
+                }
+            }
+        }
+        let all = best.len() - 1;
+        (0..best.len())
+            .map(|mine| best[mine] + within[all ^ mine])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(Network::new(data)?.alone(30).to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    Ok(Network::new(data)?.with_elephant(26).to_string())
+}
+
+#[test]
+fn test_parse() -> Result<()> {
+    let valve: Valve = "Valve HH has flow rate=22; tunnel leads to valve GG".try_into()?;
+    assert_eq!((valve.name.as_str(), valve.rate), ("HH", 22));
+    assert_eq!(valve.tunnels, vec!["GG"]);
+    let valve: Valve = "Valve AA has flow rate=0; tunnels lead to valves DD, II".try_into()?;
+    assert_eq!(valve.tunnels, vec!["DD", "II"]);
+    assert!(Valve::try_from("Valve AA has flow rate=0; no tunnels").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_compress() -> Result<()> {
+    let network = Network::new(include_str!("../sample.txt"))?;
+    assert_eq!(network.names, ["BB", "CC", "DD", "EE", "HH", "JJ", "AA"]);
+    let hh = 4;
+    let aa = 6;
+    // AA -> DD -> EE -> FF -> GG -> HH
+    assert_eq!(network.distances[aa][hh], 5);
+    assert_eq!(network.distances[hh][aa], 5);
+    assert!(Network::new("Valve AA has flow rate=0; tunnel leads to valve BB").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_many_orders() -> Result<()> {
+    // Fifteen valves a step from the start can be opened in far too many
+    // orders to try each, but reach few enough states
+    let leaves: Vec<String> = (0..15).map(|i| format!("V{i:02}")).collect();
+    let mut data = format!(
+        "Valve AA has flow rate=0; tunnels lead to valves {}\n",
+        leaves.join(", ")
+    );
+    for (i, leaf) in leaves.iter().enumerate() {
+        data += &format!(
+            "Valve {leaf} has flow rate={}; tunnel leads to valve AA\n",
+            i + 1
+        );
+    }
+    let network = Network::new(&data)?;
+    // Best first, at minutes 28, 25, 22, ... down to 1
+    let expected: u32 = (0..10).map(|i| (15 - i) * (28 - 3 * i)).sum();
+    assert_eq!(network.alone(30), expected);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "1651".to_string();
+    let got = aocday16::part1(aocday16::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "1707".to_string();
+    let got = aocday16::part2(aocday16::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
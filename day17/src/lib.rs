@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+pub mod data;
+
+pub const WIDTH: usize = 7;
+const RIGHT_WALL: u8 = 1 << (WIDTH - 1);
+
+/// A rock as rows from the bottom up, bit `x` set where column `x` is solid,
+/// already placed two columns in from the left wall.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Shape {
+    rows: [u8; 4],
+    height: usize,
+}
+impl Shape {
+    const fn new(rows: [u8; 4], height: usize) -> Self {
+        Self { rows, height }
+    }
+
+    fn rows(&self) -> &[u8] {
+        &self.rows[..self.height]
+    }
+
+    /// Pushed one column by a jet, unless that would go through a wall.
+    fn pushed(mut self, jet: Jet) -> Option<Self> {
+        let rows = &mut self.rows[..self.height];
+        match jet {
+            Jet::Left if rows.iter().all(|row| row & 1 == 0) => {
+                rows.iter_mut().for_each(|r| *r >>= 1)
+            }
+            Jet::Right if rows.iter().all(|row| row & RIGHT_WALL == 0) => {
+                rows.iter_mut().for_each(|r| *r <<= 1)
+            }
+            _ => return None,
+        }
+        Some(self)
+    }
+}
+
+const SHAPES: [Shape; 5] = [
+    Shape::new([0b0111100, 0, 0, 0], 1),
+    Shape::new([0b0001000, 0b0011100, 0b0001000, 0], 3),
+    Shape::new([0b0011100, 0b0010000, 0b0010000, 0], 3),
+    Shape::new([0b0000100; 4], 4),
+    Shape::new([0b0001100, 0b0001100, 0, 0], 2),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+pub fn parse(data: &str) -> Result<Vec<Jet>> {
+    let jets = data
+        .trim()
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => anyhow::bail!("Unexpected `{c}` at column {} of the jet pattern", i + 1),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if jets.is_empty() {
+        anyhow::bail!("The jet pattern is empty");
+    }
+    Ok(jets)
+}
+
+#[derive(Clone, Debug)]
+pub struct Chamber {
+    /// Settled rock from the floor up, one bit per column
+    rows: Vec<u8>,
+    jets: Vec<Jet>,
+    jet: usize,
+    pub rocks: usize,
+}
+impl Chamber {
+    pub fn new(data: &str) -> Result<Self> {
+        Ok(Self {
+            rows: Vec::new(),
+            jets: parse(data)?,
+            jet: 0,
+            rocks: 0,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn collides(&self, shape: &Shape, y: usize) -> bool {
+        shape.rows().iter().enumerate().any(|(i, row)| {
+            self.rows
+                .get(y + i)
+                .is_some_and(|settled| settled & row != 0)
+        })
+    }
+
+    /// Drops the next rock until it comes to rest.
+    pub fn drop_rock(&mut self) {
+        let mut shape = SHAPES[self.rocks % SHAPES.len()];
+        let mut y = self.height() + 3;
+        loop {
+            let jet = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if let Some(pushed) = shape.pushed(jet) {
+                if !self.collides(&pushed, y) {
+                    shape = pushed;
+                }
+            }
+            if y == 0 || self.collides(&shape, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        if self.rows.len() < y + shape.height {
+            self.rows.resize(y + shape.height, 0);
+        }
+        for (i, row) in shape.rows().iter().enumerate() {
+            self.rows[y + i] |= row;
+        }
+        self.rocks += 1;
+    }
+
+    /// How far below the top each column's highest rock is.
+    fn profile(&self) -> [usize; WIDTH] {
+        std::array::from_fn(|x| {
+            self.rows
+                .iter()
+                .rev()
+                .position(|row| row & (1 << x) != 0)
+                .unwrap_or(self.height())
+        })
+    }
+
+    /// Tower height once `rocks` have fallen in total.
+    ///
+    /// Once the next rock, the next jet and the shape of the top all repeat,
+    /// everything after repeats too, so whole cycles are skipped by adding
+    /// their height.
+    pub fn height_after(&mut self, rocks: usize) -> usize {
+        let mut seen = HashMap::new();
+        let mut skipped = 0;
+        while self.rocks < rocks {
+            if skipped == 0 {
+                let key = (self.rocks % SHAPES.len(), self.jet, self.profile());
+                if let Some((before, height)) = seen.insert(key, (self.rocks, self.height())) {
+                    let cycle = self.rocks - before;
+                    let cycles = (rocks - self.rocks) / cycle;
+                    skipped = cycles * (self.height() - height);
+                    // Keep the counts honest and fall the remainder for real
+                    self.rocks += cycles * cycle;
+                    if self.rocks == rocks {
+                        break;
+                    }
+                }
+            }
+            self.drop_rock();
+        }
+        self.height() + skipped
+    }
+
+    /// The top `rows` of the chamber as in the puzzle's drawings.
+    pub fn render(&self, rows: usize) -> String {
+        let mut out = String::new();
+        for row in self.rows.iter().rev().take(rows) {
+            out.push('|');
+            for x in 0..WIDTH {
+                out.push(if row & (1 << x) != 0 { '#' } else { '.' });
+            }
+            out.push_str("|\n");
+        }
+        if rows >= self.height() {
+            out.push_str("+-------+\n");
+        }
+        out
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(Chamber::new(data)?.height_after(2022).to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    Ok(Chamber::new(data)?
+        .height_after(1_000_000_000_000)
+        .to_string())
+}
+
+#[test]
+fn test_render() -> Result<()> {
+    let mut chamber = Chamber::new(include_str!("../sample.txt"))?;
+    chamber.drop_rock();
+    assert_eq!(chamber.render(10), "|..####.|\n+-------+\n");
+    chamber.drop_rock();
+    chamber.drop_rock();
+    let expected = "\
+|..#....|
+|..#....|
+|####...|
+|..###..|
+|...#...|
+|..####.|
++-------+
+";
+    assert_eq!(chamber.render(10), expected);
+    assert_eq!(chamber.render(2), "|..#....|\n|..#....|\n");
+    Ok(())
+}
+
+#[test]
+fn test_cycle() -> Result<()> {
+    let data = include_str!("../sample.txt");
+    for rocks in [0, 1, 50, 2022, 5000] {
+        let mut skipping = Chamber::new(data)?;
+        let mut simulated = Chamber::new(data)?;
+        (0..rocks).for_each(|_| simulated.drop_rock());
+        assert_eq!(skipping.height_after(rocks), simulated.height());
+    }
+    assert!(parse("<>x").is_err());
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use anyhow::Result;
+
+fn main() -> Result<()> {
+    let part1_ans = aocday17::part1(aocday17::data::part1_problem_data()?.as_str())?;
+    let part2_ans = aocday17::part2(aocday17::data::part2_problem_data()?.as_str())?;
+
+    println!("Part 1 = {part1_ans}");
+    println!("Part 2 = {part2_ans}");
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "3068".to_string();
+    let got = aocday17::part1(aocday17::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "1514285714288".to_string();
+    let got = aocday17::part2(aocday17::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
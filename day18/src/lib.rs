@@ -0,0 +1,108 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use geometry::Point3;
+pub mod data;
+
+type Cube = Point3<i64>;
+
+fn parse_cube(line: &str) -> Result<Cube> {
+    let coords = line
+        .split(',')
+        .map(|c| {
+            c.trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Bad coordinate `{c}` in `{line}`: {e}"))
+        })
+        .collect::<Result<Vec<i64>>>()?;
+    match coords[..] {
+        [x, y, z] => Ok(Point3::new(x, y, z)),
+        _ => anyhow::bail!("Expected x,y,z but got `{line}`"),
+    }
+}
+
+pub struct Droplet {
+    pub cubes: HashSet<Cube>,
+}
+impl Droplet {
+    pub fn new(data: &str) -> Result<Self> {
+        let cubes = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(parse_cube)
+            .collect::<Result<_>>()?;
+        Ok(Self { cubes })
+    }
+
+    /// Faces not shared with another cube, including those of air pockets.
+    pub fn surface_area(&self) -> usize {
+        self.cubes
+            .iter()
+            .flat_map(|c| c.neighbours6())
+            .filter(|n| !self.cubes.contains(n))
+            .count()
+    }
+
+    /// Faces reachable from outside, found by flooding the air in a box one
+    /// bigger than the droplet all round.
+    pub fn exterior_area(&self) -> usize {
+        let Some(first) = self.cubes.iter().next() else {
+            return 0;
+        };
+        let (mut min, mut max) = (*first, *first);
+        for c in &self.cubes {
+            min = Point3::new(min.x.min(c.x), min.y.min(c.y), min.z.min(c.z));
+            max = Point3::new(max.x.max(c.x), max.y.max(c.y), max.z.max(c.z));
+        }
+        let min = Point3::new(min.x - 1, min.y - 1, min.z - 1);
+        let max = Point3::new(max.x + 1, max.y + 1, max.z + 1);
+        let inside = |p: &Cube| {
+            (min.x..=max.x).contains(&p.x)
+                && (min.y..=max.y).contains(&p.y)
+                && (min.z..=max.z).contains(&p.z)
+        };
+
+        let mut faces = 0;
+        let mut seen = HashSet::from([min]);
+        let mut queue = VecDeque::from([min]);
+        while let Some(air) = queue.pop_front() {
+            for n in air.neighbours6().filter(inside) {
+                if self.cubes.contains(&n) {
+                    faces += 1;
+                } else if seen.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+        faces
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(Droplet::new(data)?.surface_area().to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    Ok(Droplet::new(data)?.exterior_area().to_string())
+}
+
+#[test]
+fn test_small() -> Result<()> {
+    let droplet = Droplet::new("1,1,1\n2,1,1")?;
+    assert_eq!(droplet.surface_area(), 10);
+    assert_eq!(droplet.exterior_area(), 10);
+
+    // A hollow 3x3x3 shell has a one cube pocket inside
+    let shell: String = (0..27)
+        .map(|i| (i % 3, i / 3 % 3, i / 9))
+        .filter(|&p| p != (1, 1, 1))
+        .map(|(x, y, z)| format!("{x},{y},{z}\n"))
+        .collect();
+    let droplet = Droplet::new(&shell)?;
+    assert_eq!(droplet.surface_area(), 54 + 6);
+    assert_eq!(droplet.exterior_area(), 54);
+
+    assert!(Droplet::new("1,2").is_err());
+    assert!(Droplet::new("1,2,x").is_err());
+    Ok(())
+}
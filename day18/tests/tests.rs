@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "64".to_string();
+    let got = aocday18::part1(aocday18::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "58".to_string();
+    let got = aocday18::part2(aocday18::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
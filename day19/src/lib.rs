@@ -0,0 +1,164 @@
+use anyhow::Result;
+use line_format::LineFormat;
+pub mod data;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Clone, Debug, LineFormat)]
+#[line_format("Blueprint {id}: Each ore robot costs {ore_ore} ore. Each clay robot costs {clay_ore} ore. Each obsidian robot costs {obsidian_ore} ore and {obsidian_clay} clay. Each geode robot costs {geode_ore} ore and {geode_obsidian} obsidian.")]
+pub struct Blueprint {
+    pub id: u32,
+    pub ore_ore: u32,
+    pub clay_ore: u32,
+    pub obsidian_ore: u32,
+    pub obsidian_clay: u32,
+    pub geode_ore: u32,
+    pub geode_obsidian: u32,
+}
+
+/// The result of searching one blueprint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Search {
+    pub id: u32,
+    pub geodes: u32,
+    /// States visited, to see how well the pruning works
+    pub nodes: u64,
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    time: u32,
+    robots: [u32; 4],
+    stock: [u32; 4],
+}
+
+impl Blueprint {
+    /// What each robot costs, indexed by robot then resource.
+    fn costs(&self) -> [[u32; 4]; 4] {
+        [
+            [self.ore_ore, 0, 0, 0],
+            [self.clay_ore, 0, 0, 0],
+            [self.obsidian_ore, self.obsidian_clay, 0, 0],
+            [self.geode_ore, 0, self.geode_obsidian, 0],
+        ]
+    }
+
+    /// Most geodes that can be open after `minutes`.
+    ///
+    /// Rather than deciding minute by minute, each branch picks the next
+    /// robot to build and skips ahead to when it can be afforded.  Robots
+    /// are capped at the most of their resource any one build can spend, and
+    /// a branch is cut once even a new geode robot every remaining minute
+    /// could not beat the best so far.
+    pub fn max_geodes(&self, minutes: u32) -> Search {
+        let costs = self.costs();
+        let mut caps = [u32::MAX; 4];
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            caps[resource] = costs.iter().map(|c| c[resource]).max().unwrap_or(0);
+        }
+        let mut search = Search {
+            id: self.id,
+            geodes: 0,
+            nodes: 0,
+        };
+        let start = State {
+            time: minutes,
+            robots: [1, 0, 0, 0],
+            stock: [0; 4],
+        };
+        explore(&costs, &caps, start, &mut search);
+        search
+    }
+}
+
+fn explore(costs: &[[u32; 4]; 4], caps: &[u32; 4], state: State, search: &mut Search) {
+    search.nodes += 1;
+    let t = state.time;
+    let idle = state.stock[GEODE] + state.robots[GEODE] * t;
+    search.geodes = search.geodes.max(idle);
+    if idle + t * t.saturating_sub(1) / 2 <= search.geodes {
+        return;
+    }
+
+    // Geode robots first so good answers are found early and cut more
+    for robot in (0..4).rev() {
+        if state.robots[robot] >= caps[robot] {
+            continue;
+        }
+        let wait = (0..4).try_fold(0, |wait, r| {
+            let cost = costs[robot][r];
+            if state.stock[r] >= cost {
+                Some(wait)
+            } else if state.robots[r] == 0 {
+                None
+            } else {
+                Some(wait.max((cost - state.stock[r]).div_ceil(state.robots[r])))
+            }
+        });
+        // A robot finished in the last minute never collects anything
+        let Some(wait) = wait.filter(|wait| wait + 1 < t) else {
+            continue;
+        };
+        let mut next = state;
+        next.time = t - wait - 1;
+        next.stock = std::array::from_fn(|r| {
+            state.stock[r] + state.robots[r] * (wait + 1) - costs[robot][r]
+        });
+        next.robots[robot] += 1;
+        explore(costs, caps, next, search);
+    }
+}
+
+pub fn parse(data: &str) -> Result<Vec<Blueprint>> {
+    data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().try_into())
+        .collect()
+}
+
+/// Searches every blueprint, each on its own thread.
+pub fn search_all(blueprints: &[Blueprint], minutes: u32) -> Vec<Search> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = blueprints
+            .iter()
+            .map(|b| scope.spawn(move || b.max_geodes(minutes)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("blueprint search panicked"))
+            .collect()
+    })
+}
+
+pub fn quality_sum(searches: &[Search]) -> u32 {
+    searches.iter().map(|s| s.id * s.geodes).sum()
+}
+
+pub fn geode_product(searches: &[Search]) -> u32 {
+    searches.iter().map(|s| s.geodes).product()
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(quality_sum(&search_all(&parse(data)?, 24)).to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let blueprints = parse(data)?;
+    let first = &blueprints[..blueprints.len().min(3)];
+    Ok(geode_product(&search_all(first, 32)).to_string())
+}
+
+#[test]
+fn test_search() -> Result<()> {
+    let blueprints = parse(include_str!("../sample.txt"))?;
+    assert_eq!(blueprints[1].geode_obsidian, 12);
+    let searches = search_all(&blueprints, 24);
+    assert_eq!(searches.iter().map(|s| s.geodes).collect::<Vec<_>>(), [9, 12]);
+    assert!(searches.iter().all(|s| s.nodes > 1));
+    assert_eq!(blueprints[0].max_geodes(0).geodes, 0);
+    assert!(parse("Blueprint 1: Each ore robot costs 4 ore.").is_err());
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use aocday19::{geode_product, parse, quality_sum, search_all};
+
+fn main() -> Result<()> {
+    let blueprints = parse(&aocday19::data::part1_problem_data()?)?;
+
+    let searches = search_all(&blueprints, 24);
+    for s in &searches {
+        println!("Blueprint {}: {} geodes, {} nodes", s.id, s.geodes, s.nodes);
+    }
+    println!("Part 1 = {}", quality_sum(&searches));
+
+    let searches = search_all(&blueprints[..blueprints.len().min(3)], 32);
+    for s in &searches {
+        println!("Blueprint {}: {} geodes, {} nodes", s.id, s.geodes, s.nodes);
+    }
+    println!("Part 2 = {}", geode_product(&searches));
+    Ok(())
+}
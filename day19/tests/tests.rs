@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "33".to_string();
+    let got = aocday19::part1(aocday19::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "3472".to_string();
+    let got = aocday19::part2(aocday19::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected,got);
+    Ok(())
+}
\ No newline at end of file
@@ -123,6 +123,45 @@ impl TryFrom<&str> for Goal {
     }
 }
 
+/// Total score reading the second column as the hand to play.
+pub fn part1(data: &str) -> Result<String> {
+    let rounds: Result<Vec<Round>> = data
+        .lines()
+        .map(|line| {
+            let (opp, me) = line
+                .split_once(' ')
+                .ok_or_else(|| anyhow::anyhow!("Could not split round {line}"))?;
+            Ok(Round {
+                opp: opp.try_into()?,
+                me: me.try_into()?,
+            })
+        })
+        .collect();
+    let total: u32 = rounds?.iter().map(|r| r.score().1).sum();
+    Ok(total.to_string())
+}
+
+/// Total score reading the second column as the result to achieve.
+pub fn part2(data: &str) -> Result<String> {
+    let goals: Result<Vec<Goal>> = data.lines().map(|line| line.try_into()).collect();
+    let total: u32 = goals?
+        .into_iter()
+        .map(|goal| {
+            let me = goal.opp.to_achieve(goal.result);
+            Round { opp: goal.opp, me }.score().1
+        })
+        .sum();
+    Ok(total.to_string())
+}
+
+#[test]
+fn test_parts() -> Result<()> {
+    let sample = include_str!("../sample.txt");
+    assert_eq!(part1(sample)?, "15");
+    assert_eq!(part2(sample)?, "12");
+    Ok(())
+}
+
 #[test]
 fn test_rounds() -> Result<()> {
     let sample = include_str!("../sample.txt");
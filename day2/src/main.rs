@@ -2,7 +2,7 @@ use anyhow::Result;
 use day2::{Round, Goal};
 
 fn main() -> Result<()> {
-    let data = include_str!("../problem.txt");
+    let data = &puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))?;
 
     let goals: Result<Vec<Goal>> = data
         .lines()
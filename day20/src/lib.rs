@@ -0,0 +1,157 @@
+use anyhow::Result;
+pub mod data;
+
+pub const DECRYPTION_KEY: i64 = 811_589_153;
+
+pub fn parse(data: &str) -> Result<Vec<i64>> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Bad number `{line}` on line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// The circle of original positions, split into blocks of about `√n` so that
+/// finding, removing and inserting an element each take `O(√n)` rather than
+/// shifting the whole list.
+struct Circle {
+    blocks: Vec<Vec<usize>>,
+    /// Which block each original position is in
+    block_of: Vec<usize>,
+    block_size: usize,
+}
+impl Circle {
+    fn new(len: usize) -> Self {
+        let block_size = (len as f64).sqrt().ceil().max(1.0) as usize;
+        let mut circle = Self {
+            blocks: vec![(0..len).collect()],
+            block_of: vec![0; len],
+            block_size,
+        };
+        circle.rebuild();
+        circle
+    }
+
+    /// Re-splits into even blocks once inserts have made one too long.
+    fn rebuild(&mut self) {
+        let order: Vec<usize> = self.blocks.concat();
+        self.blocks = order.chunks(self.block_size).map(|c| c.to_vec()).collect();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for &id in block {
+                self.block_of[id] = b;
+            }
+        }
+    }
+
+    fn remove(&mut self, id: usize) -> usize {
+        let b = self.block_of[id];
+        let offset = self.blocks[b].iter().position(|&x| x == id).unwrap();
+        self.blocks[b].remove(offset);
+        self.blocks[..b].iter().map(Vec::len).sum::<usize>() + offset
+    }
+
+    fn insert(&mut self, mut index: usize, id: usize) {
+        let last = self.blocks.len() - 1;
+        for b in 0..self.blocks.len() {
+            if index <= self.blocks[b].len() && (index < self.blocks[b].len() || b == last) {
+                self.blocks[b].insert(index, id);
+                self.block_of[id] = b;
+                if self.blocks[b].len() > 2 * self.block_size {
+                    self.rebuild();
+                }
+                return;
+            }
+            index -= self.blocks[b].len();
+        }
+    }
+
+    fn order(&self) -> Vec<usize> {
+        self.blocks.concat()
+    }
+}
+
+/// Moves every number by its value, in original order, `rounds` times, and
+/// returns the numbers in their final order starting from the first.
+pub fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let len = numbers.len();
+    if len < 2 {
+        return numbers.to_vec();
+    }
+    let mut circle = Circle::new(len);
+    for _ in 0..rounds {
+        for (id, &value) in numbers.iter().enumerate() {
+            let from = circle.remove(id);
+            // With the number lifted out there are len - 1 gaps to land in
+            let to = (from as i64 + value).rem_euclid(len as i64 - 1);
+            circle.insert(to as usize, id);
+        }
+    }
+    circle.order().into_iter().map(|id| numbers[id]).collect()
+}
+
+/// The 1000th, 2000th and 3000th numbers after the zero, added up.
+pub fn grove_coordinates(mixed: &[i64]) -> Result<i64> {
+    let zero = mixed
+        .iter()
+        .position(|&n| n == 0)
+        .ok_or_else(|| anyhow::anyhow!("There is no 0 in the file"))?;
+    Ok([1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum())
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    Ok(grove_coordinates(&mix(&parse(data)?, 1))?.to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let numbers: Vec<i64> = parse(data)?.iter().map(|n| n * DECRYPTION_KEY).collect();
+    Ok(grove_coordinates(&mix(&numbers, 10))?.to_string())
+}
+
+/// Mixing by shifting a plain vector, to check against.
+#[cfg(test)]
+fn mix_slowly(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let len = numbers.len() as i64;
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    for _ in 0..rounds {
+        for (id, &value) in numbers.iter().enumerate() {
+            let from = order.iter().position(|&x| x == id).unwrap();
+            order.remove(from);
+            let to = (from as i64 + value).rem_euclid(len - 1);
+            order.insert(to as usize, id);
+        }
+    }
+    order.into_iter().map(|id| numbers[id]).collect()
+}
+
+#[test]
+fn test_mix() {
+    // Circular, so compare from the zero
+    let rotate = |v: Vec<i64>| {
+        let zero = v.iter().position(|&n| n == 0).unwrap();
+        [&v[zero..], &v[..zero]].concat()
+    };
+    let sample = [1, 2, -3, 3, -2, 0, 4];
+    assert_eq!(rotate(mix(&sample, 1)), [0, 3, -2, 1, 2, -3, 4]);
+
+    // Duplicates and values far bigger than the list
+    let mut seed = 7u64;
+    let numbers: Vec<i64> = (0..300)
+        .map(|i| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            if i == 150 {
+                0
+            } else {
+                (seed >> 33) as i64 % 20 - 10 + if i % 7 == 0 { 1_000_003 } else { 0 }
+            }
+        })
+        .collect();
+    assert_eq!(rotate(mix(&numbers, 3)), rotate(mix_slowly(&numbers, 3)));
+    assert_eq!(mix(&[5], 1), [5]);
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use expression::Expr;
+pub mod data;
+
+pub const ROOT: &str = "root";
+pub const HUMAN: &str = "humn";
+
+/// What every monkey yells, by name.
+pub struct Troop {
+    pub jobs: HashMap<String, Expr>,
+}
+impl Troop {
+    pub fn new(data: &str) -> Result<Self> {
+        let mut jobs = HashMap::new();
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, job) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow::anyhow!("Expected `name: job` on line {}", i + 1))?;
+            let job = Expr::parse(job).map_err(|e| anyhow::anyhow!("line {}: {e}", i + 1))?;
+            if jobs.insert(name.trim().to_string(), job).is_some() {
+                anyhow::bail!("Monkey {name} appears twice");
+            }
+        }
+        let troop = Self { jobs };
+        troop.check_cycles()?;
+        Ok(troop)
+    }
+
+    /// A monkey waiting, however indirectly, on itself would never yell.
+    fn check_cycles(&self) -> Result<()> {
+        fn visit<'a>(
+            troop: &'a Troop,
+            name: &'a str,
+            done: &mut HashMap<&'a str, bool>,
+        ) -> Result<()> {
+            match done.get(name) {
+                Some(true) => return Ok(()),
+                Some(false) => anyhow::bail!("Monkey {name} is waiting on itself"),
+                None => {}
+            }
+            done.insert(name, false);
+            let mut waits = Vec::new();
+            if let Some(job) = troop.jobs.get(name) {
+                names(job, &mut waits);
+            }
+            for other in waits {
+                visit(troop, other, done)?;
+            }
+            done.insert(name, true);
+            Ok(())
+        }
+        fn names<'a>(expr: &'a Expr, out: &mut Vec<&'a str>) {
+            match expr {
+                Expr::Num(_) => {}
+                Expr::Var(name) => out.push(name),
+                Expr::Binary(left, _, right) => {
+                    names(left, out);
+                    names(right, out);
+                }
+            }
+        }
+
+        let mut done = HashMap::new();
+        for name in self.jobs.keys() {
+            visit(self, name, &mut done)?;
+        }
+        Ok(())
+    }
+
+    /// The whole calculation behind `name` as one expression, leaving
+    /// `unknown` as a variable if given.
+    pub fn tree(&self, name: &str, unknown: Option<&str>) -> Result<Expr> {
+        Expr::Var(name.to_string()).substitute(&|n| {
+            (Some(n) != unknown).then(|| {
+                self.jobs
+                    .get(n)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("No monkey named {n}"))
+            })
+        })
+    }
+}
+
+fn no_vars(_: &str) -> Option<i128> {
+    None
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let troop = Troop::new(data)?;
+    Ok(troop.tree(ROOT, None)?.eval(&no_vars)?.to_string())
+}
+
+/// The number to yell so both sides of `root` match.
+pub fn part2(data: &str) -> Result<String> {
+    let troop = Troop::new(data)?;
+    let Expr::Binary(left, _, right) = troop.tree(ROOT, Some(HUMAN))? else {
+        anyhow::bail!("{ROOT} should compare two monkeys");
+    };
+    let (unknown, known) = if left.contains(HUMAN) {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let target = known.eval(&no_vars)?;
+    Ok(unknown.solve(HUMAN, target, &no_vars)?.to_string())
+}
+
+#[test]
+fn test_troop() -> Result<()> {
+    let troop = Troop::new(include_str!("../sample.txt"))?;
+    assert_eq!(troop.jobs.len(), 15);
+    assert_eq!(troop.tree("drzm", None)?.to_string(), "(32 - 2)");
+    assert_eq!(troop.tree("ptdq", Some(HUMAN))?.to_string(), "(humn - 3)");
+
+    assert!(Troop::new("root: a + b\na: b * 2\nb: a - 1").is_err());
+    assert!(Troop::new("root: a + b\nroot: 4").is_err());
+    assert!(Troop::new("root 4").is_err());
+    assert!(part1("root: a + 2").is_err());
+    Ok(())
+}
@@ -0,0 +1,352 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+use geometry::{Dir4, Point, Vector, Vector3};
+pub mod data;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+pub fn parse_path(line: &str) -> Result<Vec<Step>> {
+    let mut steps = Vec::new();
+    let mut number = None;
+    for (i, c) in line.trim().char_indices() {
+        if let Some(digit) = c.to_digit(10) {
+            number = Some(number.unwrap_or(0u32) * 10 + digit);
+            continue;
+        }
+        steps.extend(number.take().map(Step::Forward));
+        steps.push(match c {
+            'L' => Step::Left,
+            'R' => Step::Right,
+            _ => anyhow::bail!("Unexpected `{c}` at column {} of the path", i + 1),
+        });
+    }
+    steps.extend(number.map(Step::Forward));
+    Ok(steps)
+}
+
+/// The puzzle scores facing right as 0, then clockwise.
+pub fn facing_value(dir: Dir4) -> i64 {
+    match dir {
+        Dir4::Right => 0,
+        Dir4::Down => 1,
+        Dir4::Left => 2,
+        Dir4::Up => 3,
+    }
+}
+
+pub struct Board {
+    /// Rows as given, ragged, with spaces off the map
+    rows: Vec<Vec<u8>>,
+    pub path: Vec<Step>,
+}
+impl Board {
+    pub fn new(data: &str) -> Result<Self> {
+        let (map, path) = data
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow::anyhow!("Expected the map, a blank line and the path"))?;
+        let rows: Vec<Vec<u8>> = map
+            .lines()
+            .map(|l| l.trim_end().as_bytes().to_vec())
+            .collect();
+        if let Some(c) = rows.iter().flatten().find(|c| !b" .#".contains(c)) {
+            anyhow::bail!("Unexpected `{}` in the map", *c as char);
+        }
+        Ok(Self {
+            rows,
+            path: parse_path(path)?,
+        })
+    }
+
+    pub fn get(&self, p: Point<i64>) -> u8 {
+        let (Ok(x), Ok(y)) = (usize::try_from(p.x), usize::try_from(p.y)) else {
+            return b' ';
+        };
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(b' ')
+    }
+
+    fn on_map(&self, p: Point<i64>) -> bool {
+        self.get(p) != b' '
+    }
+
+    /// The leftmost open tile of the top row, facing right.
+    pub fn start(&self) -> Result<Point<i64>> {
+        let x = self
+            .rows
+            .first()
+            .and_then(|row| row.iter().position(|&c| c == b'.'))
+            .ok_or_else(|| anyhow::anyhow!("The top row has no open tile"))?;
+        Ok(Point::new(x as i64, 0))
+    }
+
+    /// Follows the path, wrapping off the edge of the map with `wrap`, and
+    /// returns every tile stood on with the way it was faced.
+    pub fn walk(&self, wrap: &impl Wrap) -> Result<Vec<(Point<i64>, Dir4)>> {
+        let mut pos = self.start()?;
+        let mut dir = Dir4::Right;
+        let mut trace = vec![(pos, dir)];
+        for step in &self.path {
+            match step {
+                Step::Left => dir = dir.turn_left(),
+                Step::Right => dir = dir.turn_right(),
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let ahead = pos + dir.vector();
+                        let (next, next_dir) = if self.on_map(ahead) {
+                            (ahead, dir)
+                        } else {
+                            wrap.wrap(self, pos, dir)
+                        };
+                        if self.get(next) == b'#' {
+                            break;
+                        }
+                        (pos, dir) = (next, next_dir);
+                        trace.push((pos, dir));
+                    }
+                }
+            }
+            trace.push((pos, dir));
+        }
+        Ok(trace)
+    }
+
+    /// The map with the walked path drawn over it, as in the puzzle.
+    pub fn render(&self, trace: &[(Point<i64>, Dir4)]) -> String {
+        let last: HashMap<_, _> = trace.iter().copied().collect();
+        let mut out = String::new();
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                out.push(match last.get(&Point::new(x as i64, y as i64)) {
+                    Some(Dir4::Right) => '>',
+                    Some(Dir4::Down) => 'v',
+                    Some(Dir4::Left) => '<',
+                    Some(Dir4::Up) => '^',
+                    None => c as char,
+                });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+pub fn password(trace: &[(Point<i64>, Dir4)]) -> Result<i64> {
+    let (pos, dir) = trace
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("Never started walking"))?;
+    Ok(1000 * (pos.y + 1) + 4 * (pos.x + 1) + facing_value(*dir))
+}
+
+/// Where walking off the map at `pos` facing `dir` comes back on, and which
+/// way it is then facing.
+pub trait Wrap {
+    fn wrap(&self, board: &Board, pos: Point<i64>, dir: Dir4) -> (Point<i64>, Dir4);
+}
+
+/// Part 1: back round to the far side of the same row or column.
+pub struct Flat;
+impl Wrap for Flat {
+    fn wrap(&self, board: &Board, pos: Point<i64>, dir: Dir4) -> (Point<i64>, Dir4) {
+        let back = dir.opposite().vector();
+        let mut p = pos;
+        while board.on_map(p + back) {
+            p += back;
+        }
+        (p, dir)
+    }
+}
+
+/// One face of the folded cube, described by where its top left tile is on
+/// the map and which way its right, down and outward directions point in 3D.
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    origin: Point<i64>,
+    right: Vector3<i64>,
+    down: Vector3<i64>,
+    normal: Vector3<i64>,
+}
+impl Face {
+    fn direction(&self, dir: Dir4) -> Vector3<i64> {
+        match dir {
+            Dir4::Right => self.right,
+            Dir4::Left => -self.right,
+            Dir4::Down => self.down,
+            Dir4::Up => -self.down,
+        }
+    }
+
+    /// Rolled over the edge in direction `dir` of the net.
+    fn fold(&self, dir: Dir4, size: i64) -> Face {
+        let Face {
+            right,
+            down,
+            normal,
+            ..
+        } = *self;
+        let (right, down, normal) = match dir {
+            Dir4::Right => (-normal, down, right),
+            Dir4::Left => (normal, down, -right),
+            Dir4::Down => (right, -normal, down),
+            Dir4::Up => (right, normal, -down),
+        };
+        Face {
+            origin: self.origin + dir.vector() * size,
+            right,
+            down,
+            normal,
+        }
+    }
+}
+
+/// Part 2: the map folded into a cube.
+///
+/// The faces are found by rolling an imaginary cube across the net from the
+/// first face, which tells each face its orientation in 3D, so any of the
+/// eleven nets works.  Tiles are placed in 3D at doubled coordinates, on a
+/// cube from `-size` to `size`, so every tile centre is a whole number.
+pub struct Cube {
+    size: i64,
+    faces: Vec<Face>,
+}
+impl Cube {
+    pub fn new(board: &Board) -> Result<Self> {
+        let tiles = board.rows.iter().flatten().filter(|&&c| c != b' ').count();
+        let size = ((tiles / 6) as f64).sqrt().round() as i64;
+        if size == 0 || 6 * size * size != tiles as i64 {
+            anyhow::bail!("{tiles} tiles cannot fold into a cube");
+        }
+
+        let first = Face {
+            origin: board.start()?,
+            right: Vector3::new(1, 0, 0),
+            down: Vector3::new(0, 1, 0),
+            normal: Vector3::new(0, 0, 1),
+        };
+        let first = Face {
+            origin: Point::new(first.origin.x / size * size, 0),
+            ..first
+        };
+        let mut faces = vec![first];
+        let mut queue = VecDeque::from([first]);
+        while let Some(face) = queue.pop_front() {
+            for dir in Dir4::ALL {
+                let next = face.fold(dir, size);
+                if board.on_map(next.origin) && !faces.iter().any(|f| f.origin == next.origin) {
+                    faces.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        if faces.len() != 6 {
+            anyhow::bail!("The map has {} faces, not 6", faces.len());
+        }
+        if Vector3::FACES
+            .iter()
+            .any(|&n| !faces.iter().any(|f| f.normal == n))
+        {
+            anyhow::bail!("The faces overlap when folded, so the map is not a cube net");
+        }
+        Ok(Self { size, faces })
+    }
+
+    fn face_at(&self, p: Point<i64>) -> &Face {
+        let corner = Point::new(p.x / self.size * self.size, p.y / self.size * self.size);
+        self.faces.iter().find(|f| f.origin == corner).unwrap()
+    }
+}
+impl Wrap for Cube {
+    fn wrap(&self, _: &Board, pos: Point<i64>, dir: Dir4) -> (Point<i64>, Dir4) {
+        let n = self.size;
+        let from = self.face_at(pos);
+        let heading = from.direction(dir);
+        let to = self.faces.iter().find(|f| f.normal == heading).unwrap();
+
+        let local = pos - from.origin;
+        let centre = from.normal * n
+            + from.right * (2 * local.x + 1 - n)
+            + from.down * (2 * local.y + 1 - n);
+        // Over the edge and down the next face
+        let centre = centre + heading - from.normal;
+        let x = (centre.dot(to.right) + n - 1) / 2;
+        let y = (centre.dot(to.down) + n - 1) / 2;
+
+        let down_the_face = -from.normal;
+        let dir = Dir4::ALL
+            .into_iter()
+            .find(|&d| to.direction(d) == down_the_face)
+            .unwrap();
+        (to.origin + Vector::new(x, y), dir)
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let board = Board::new(data)?;
+    Ok(password(&board.walk(&Flat)?)?.to_string())
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let board = Board::new(data)?;
+    let cube = Cube::new(&board)?;
+    Ok(password(&board.walk(&cube)?)?.to_string())
+}
+
+#[test]
+fn test_path() -> Result<()> {
+    use Step::*;
+    assert_eq!(parse_path("10R5L")?, [Forward(10), Right, Forward(5), Left]);
+    assert!(parse_path("10X").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_render() -> Result<()> {
+    let board = Board::new(include_str!("../sample.txt"))?;
+    let render = board.render(&board.walk(&Flat)?);
+    let lines: Vec<&str> = render.lines().collect();
+    assert_eq!(lines[0], "        >>v#");
+    assert_eq!(lines[5], ">>>v...>#.>>");
+    Ok(())
+}
+
+#[test]
+fn test_cube_edges() -> Result<()> {
+    // Walking off any edge and straight back must return to the same tile,
+    // for the sample's net and for a cross shaped one
+    let cross = [
+        "  ..", "  ..", "......", "......", "  ..", "  ..", "  ..", "  ..", "", "1",
+    ]
+    .join("\n");
+    for data in [include_str!("../sample.txt"), cross.as_str()] {
+        let board = Board::new(data)?;
+        let cube = Cube::new(&board)?;
+        for y in 0..board.rows.len() as i64 {
+            for x in 0..board.rows[y as usize].len() as i64 {
+                let pos = Point::new(x, y);
+                for dir in Dir4::ALL.into_iter().filter(|_| board.on_map(pos)) {
+                    if board.on_map(pos + dir.vector()) {
+                        continue;
+                    }
+                    let (there, facing) = cube.wrap(&board, pos, dir);
+                    assert!(board.on_map(there));
+                    assert_eq!(
+                        cube.wrap(&board, there, facing.opposite()),
+                        (pos, dir.opposite())
+                    );
+                }
+            }
+        }
+    }
+    assert!(Cube::new(&Board::new("....\n\n1")?).is_err());
+    // Six faces in a row fold round onto themselves
+    assert!(Cube::new(&Board::new("......\n\n1")?).is_err());
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "6032".to_string();
+    let got = aocday22::part1(aocday22::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "5031".to_string();
+    let got = aocday22::part2(aocday22::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use geometry::{Dir8, Point};
+pub mod data;
+
+/// The directions considered in the first round, each with the three
+/// neighbours that must be empty to go that way.  Every round the first
+/// moves to the back.
+const PRIORITY: [(Dir8, [Dir8; 3]); 4] = [
+    (Dir8::N, [Dir8::N, Dir8::NE, Dir8::NW]),
+    (Dir8::S, [Dir8::S, Dir8::SE, Dir8::SW]),
+    (Dir8::W, [Dir8::W, Dir8::NW, Dir8::SW]),
+    (Dir8::E, [Dir8::E, Dir8::NE, Dir8::SE]),
+];
+
+/// The elves, on ground that goes on for ever in every direction.
+#[derive(Clone, Debug)]
+pub struct Grove {
+    pub elves: HashSet<Point<i64>>,
+    /// Rounds played so far
+    pub round: usize,
+}
+impl Grove {
+    pub fn new(data: &str) -> Result<Self> {
+        let mut elves = HashSet::new();
+        for (y, line) in data.lines().enumerate() {
+            for (x, c) in line.trim_end().chars().enumerate() {
+                match c {
+                    '#' => {
+                        elves.insert(Point::new(x as i64, y as i64));
+                    }
+                    '.' => {}
+                    _ => anyhow::bail!("Unexpected `{c}` at line {} column {}", y + 1, x + 1),
+                }
+            }
+        }
+        Ok(Self { elves, round: 0 })
+    }
+
+    fn occupied(&self, p: Point<i64>, dir: Dir8) -> bool {
+        self.elves.contains(&(p + dir.vector()))
+    }
+
+    /// Where the elf at `p` would like to go, if anywhere.
+    fn proposal(&self, p: Point<i64>) -> Option<Point<i64>> {
+        if !Dir8::ALL.iter().any(|&d| self.occupied(p, d)) {
+            return None;
+        }
+        (0..PRIORITY.len())
+            .map(|i| PRIORITY[(self.round + i) % PRIORITY.len()])
+            .find(|(_, looks)| !looks.iter().any(|&d| self.occupied(p, d)))
+            .map(|(dir, _)| p + dir.vector())
+    }
+
+    /// Plays one round, returning how many elves moved.
+    pub fn step(&mut self) -> usize {
+        // Target -> the elf heading there, or None once two want it
+        let mut proposals: HashMap<Point<i64>, Option<Point<i64>>> = HashMap::new();
+        for &elf in &self.elves {
+            if let Some(to) = self.proposal(elf) {
+                proposals
+                    .entry(to)
+                    .and_modify(|from| *from = None)
+                    .or_insert(Some(elf));
+            }
+        }
+        let mut moved = 0;
+        for (to, from) in proposals {
+            if let Some(from) = from {
+                self.elves.remove(&from);
+                self.elves.insert(to);
+                moved += 1;
+            }
+        }
+        self.round += 1;
+        moved
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle
+    /// holding every elf.
+    pub fn bounds(&self) -> Option<(Point<i64>, Point<i64>)> {
+        let mut elves = self.elves.iter();
+        let first = *elves.next()?;
+        Some(elves.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    pub fn empty_ground(&self) -> usize {
+        match self.bounds() {
+            Some((min, max)) => {
+                let area = (max.x - min.x + 1) * (max.y - min.y + 1);
+                area as usize - self.elves.len()
+            }
+            None => 0,
+        }
+    }
+
+    /// The bounding rectangle drawn as in the puzzle.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some((min, max)) = self.bounds() {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    out.push(if self.elves.contains(&Point::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    });
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let mut grove = Grove::new(data)?;
+    for _ in 0..10 {
+        grove.step();
+    }
+    Ok(grove.empty_ground().to_string())
+}
+
+/// The first round in which no elf moves.
+pub fn part2(data: &str) -> Result<String> {
+    let mut grove = Grove::new(data)?;
+    while grove.step() > 0 {}
+    Ok(grove.round.to_string())
+}
+
+#[test]
+fn test_rounds() -> Result<()> {
+    let mut grove = Grove::new(".....\n..##.\n..#..\n.....\n..##.\n.....")?;
+    assert_eq!(grove.step(), 3);
+    assert_eq!(grove.render(), "##\n..\n#.\n.#\n#.\n");
+    grove.step();
+    assert_eq!(grove.step(), 3);
+    assert_eq!(grove.render(), "..#..\n....#\n#....\n....#\n.....\n..#..\n");
+    assert_eq!(grove.step(), 0);
+    assert_eq!(grove.empty_ground(), 25);
+
+    assert_eq!(Grove::new("")?.empty_ground(), 0);
+    assert!(Grove::new(".x").is_err());
+    Ok(())
+}
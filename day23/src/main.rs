@@ -0,0 +1,22 @@
+use anyhow::Result;
+use aocday23::Grove;
+
+/// Run with `--dump` to see the grove after each of the first ten rounds.
+fn main() -> Result<()> {
+    let data = aocday23::data::part1_problem_data()?;
+    if std::env::args().any(|arg| arg == "--dump") {
+        let mut grove = Grove::new(&data)?;
+        println!("== Initial State ==\n{}", grove.render());
+        for _ in 0..10 {
+            grove.step();
+            println!("== End of Round {} ==\n{}", grove.round, grove.render());
+        }
+    }
+
+    let part1_ans = aocday23::part1(data.as_str())?;
+    let part2_ans = aocday23::part2(aocday23::data::part2_problem_data()?.as_str())?;
+
+    println!("Part 1 = {part1_ans}");
+    println!("Part 2 = {part2_ans}");
+    Ok(())
+}
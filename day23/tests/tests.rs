@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "110".to_string();
+    let got = aocday23::part1(aocday23::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "20".to_string();
+    let got = aocday23::part2(aocday23::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
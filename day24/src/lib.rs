@@ -0,0 +1,213 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use geometry::{Dir4, Point};
+pub mod data;
+
+/// The basin as first seen.  Blizzards never change row or column and wrap
+/// round inside the walls, so rather than moving them the valley works out
+/// where each one started and checks whether it has reached a tile yet.
+pub struct Valley {
+    /// The starting blizzards of the interior, one row per line
+    rows: Vec<Vec<u8>>,
+    width: i64,
+    height: i64,
+    pub start: Point<i64>,
+    pub goal: Point<i64>,
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Valley {
+    pub fn new(data: &str) -> Result<Self> {
+        let lines: Vec<&str> = data
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        if lines.len() < 3 {
+            anyhow::bail!("Expected a wall, at least one row of valley and a wall");
+        }
+        let gap = |line: &str| -> Result<i64> {
+            let mut gaps = line.match_indices('.');
+            match (gaps.next(), gaps.next()) {
+                (Some((x, _)), None) => Ok(x as i64),
+                _ => anyhow::bail!("Expected exactly one gap in the wall `{line}`"),
+            }
+        };
+        let start = Point::new(gap(lines[0])?, 0);
+        let goal = Point::new(gap(lines[lines.len() - 1])?, lines.len() as i64 - 1);
+
+        let mut rows = Vec::new();
+        for (i, line) in lines[1..lines.len() - 1].iter().enumerate() {
+            let bytes = line.as_bytes();
+            if bytes.len() < 3 || bytes[0] != b'#' || bytes[bytes.len() - 1] != b'#' {
+                anyhow::bail!("Line {} should be walled in: `{line}`", i + 2);
+            }
+            let row = bytes[1..bytes.len() - 1].to_vec();
+            if let Some(c) = row.iter().find(|c| !b".<>^v".contains(c)) {
+                anyhow::bail!("Unexpected `{}` on line {}", *c as char, i + 2);
+            }
+            if rows
+                .first()
+                .is_some_and(|first: &Vec<u8>| first.len() != row.len())
+            {
+                anyhow::bail!("Line {} is not as wide as the others", i + 2);
+            }
+            rows.push(row);
+        }
+
+        let width = rows[0].len() as i64;
+        for gap in [start.x, goal.x] {
+            if !(1..=width).contains(&gap) {
+                anyhow::bail!("The gap in column {} is in the side wall", gap + 1);
+            }
+            // A blizzard blowing along a gap's column would escape the valley
+            if rows
+                .iter()
+                .any(|row| b"^v".contains(&row[gap as usize - 1]))
+            {
+                anyhow::bail!("A blizzard blows out through the gap in column {}", gap + 1);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height: rows.len() as i64,
+            rows,
+            start,
+            goal,
+        })
+    }
+
+    /// After this many minutes every blizzard is back where it started.
+    pub fn period(&self) -> usize {
+        let (w, h) = (self.width as usize, self.height as usize);
+        w / gcd(w, h) * h
+    }
+
+    /// What started at interior tile (`x`, `y`), wrapping round the walls.
+    fn started_at(&self, x: i64, y: i64) -> u8 {
+        self.rows[y.rem_euclid(self.height) as usize][x.rem_euclid(self.width) as usize]
+    }
+
+    /// Whether `p` is clear of walls and blizzards at minute `time`.
+    pub fn is_clear(&self, p: Point<i64>, time: usize) -> bool {
+        if p == self.start || p == self.goal {
+            return true;
+        }
+        if !(1..=self.width).contains(&p.x) || !(1..=self.height).contains(&p.y) {
+            return false;
+        }
+        let (x, y) = (p.x - 1, p.y - 1);
+        let t = time as i64;
+        let at = |x, y| self.started_at(x, y);
+        at(x - t, y) != b'>' && at(x + t, y) != b'<' && at(x, y - t) != b'v' && at(x, y + t) != b'^'
+    }
+
+    /// The earliest minute `to` can be reached leaving `from` at minute
+    /// `time`.
+    ///
+    /// A breadth first search over (position, minute), where the neighbours
+    /// of a state are the clear tiles a step away or the same tile one minute
+    /// on.  As the blizzards repeat, two visits to a tile at the same point
+    /// in the cycle are the same state.
+    pub fn crossing(&self, from: Point<i64>, to: Point<i64>, time: usize) -> Result<usize> {
+        let period = self.period();
+        let mut seen = HashSet::from([(from, time % period)]);
+        let mut queue = VecDeque::from([(from, time)]);
+        while let Some((p, t)) = queue.pop_front() {
+            if p == to {
+                return Ok(t);
+            }
+            let moves = Dir4::ALL.into_iter().map(|d| p + d.vector());
+            for next in moves.chain(std::iter::once(p)) {
+                if self.is_clear(next, t + 1) && seen.insert((next, (t + 1) % period)) {
+                    queue.push_back((next, t + 1));
+                }
+            }
+        }
+        anyhow::bail!("There is no way from {from:?} to {to:?}")
+    }
+
+    /// The valley at minute `time`, drawn as in the puzzle.
+    pub fn render(&self, time: usize) -> String {
+        let t = time as i64;
+        let wall = |gap: i64| -> String {
+            (0..self.width + 2)
+                .map(|x| if x == gap { '.' } else { '#' })
+                .collect()
+        };
+        let mut out = wall(self.start.x);
+        out.push('\n');
+        for y in 0..self.height {
+            out.push('#');
+            for x in 0..self.width {
+                let here: Vec<char> = [
+                    (self.started_at(x - t, y), b'>'),
+                    (self.started_at(x + t, y), b'<'),
+                    (self.started_at(x, y - t), b'v'),
+                    (self.started_at(x, y + t), b'^'),
+                ]
+                .iter()
+                .filter(|(c, b)| c == b)
+                .map(|(c, _)| *c as char)
+                .collect();
+                out.push(match here.len() {
+                    0 => '.',
+                    1 => here[0],
+                    n => char::from_digit(n as u32, 10).unwrap(),
+                });
+            }
+            out.push_str("#\n");
+        }
+        out.push_str(&wall(self.goal.x));
+        out.push('\n');
+        out
+    }
+}
+
+pub fn part1(data: &str) -> Result<String> {
+    let valley = Valley::new(data)?;
+    Ok(valley.crossing(valley.start, valley.goal, 0)?.to_string())
+}
+
+/// Across, back for the snacks, and across again.
+pub fn part2(data: &str) -> Result<String> {
+    let valley = Valley::new(data)?;
+    let there = valley.crossing(valley.start, valley.goal, 0)?;
+    let back = valley.crossing(valley.goal, valley.start, there)?;
+    Ok(valley
+        .crossing(valley.start, valley.goal, back)?
+        .to_string())
+}
+
+#[test]
+fn test_valley() -> Result<()> {
+    let valley = Valley::new(include_str!("../sample.txt"))?;
+    assert_eq!(valley.period(), 12);
+    assert_eq!(valley.crossing(valley.start, valley.goal, 0)?, 18);
+    assert_eq!(valley.crossing(valley.goal, valley.start, 18)?, 41);
+    assert_eq!(valley.render(12), valley.render(0));
+    assert_eq!(
+        valley.render(1),
+        "#.######\n#.>3.<.#\n#<..<<.#\n#>2.22.#\n#>v..^<#\n######.#\n"
+    );
+
+    assert!(Valley::new("#.###\n#.<.#\n###.#").is_ok());
+    assert!(Valley::new("#.###\n#v..#\n###.#").is_err());
+    assert!(Valley::new("#.###\n#...\n###.#").is_err());
+    // Gaps in the corners, where the side walls are
+    assert!(Valley::new(".####\n#...#\n###.#").is_err());
+    assert!(Valley::new("#.###\n#...#\n####.").is_err());
+    // A blizzard that can only blow in place blocks the one way through
+    let blocked = Valley::new("#.#\n#>#\n#.#")?;
+    assert!(blocked.crossing(blocked.start, blocked.goal, 0).is_err());
+    Ok(())
+}
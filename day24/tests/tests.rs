@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "18".to_string();
+    let got = aocday24::part1(aocday24::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "54".to_string();
+    let got = aocday24::part2(aocday24::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+use anyhow::Result;
+pub mod data;
+
+/// A number in SNAFU, balanced base five: digits run from `=` (-2) and `-`
+/// (-1) through `0`, `1` and `2`, so every integer, negative or not, has
+/// exactly one spelling and there is no sign.
+///
+/// The digits are kept rather than the value, least significant first and
+/// without zeros at the top, so numbers of any size can be written, read and
+/// added.  Only converting to a machine integer can overflow.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Snafu {
+    digits: Vec<i8>,
+}
+
+impl Snafu {
+    fn from_digits(mut digits: Vec<i8>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Self { digits }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.is_empty() {
+            anyhow::bail!("A SNAFU number needs at least one digit");
+        }
+        let digits = s
+            .chars()
+            .rev()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '=' => Ok(-2),
+                '-' => Ok(-1),
+                '0' => Ok(0),
+                '1' => Ok(1),
+                '2' => Ok(2),
+                _ => anyhow::bail!(
+                    "Unexpected `{c}` at column {} of `{s}`",
+                    s.chars().count() - i
+                ),
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::from_digits(digits))
+    }
+}
+
+impl TryFrom<&str> for Snafu {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.digits.is_empty() {
+            return f.write_str("0");
+        }
+        let text: String = self
+            .digits
+            .iter()
+            .rev()
+            .map(|d| match d {
+                -2 => '=',
+                -1 => '-',
+                0 => '0',
+                1 => '1',
+                _ => '2',
+            })
+            .collect();
+        f.pad(&text)
+    }
+}
+
+impl From<i128> for Snafu {
+    fn from(mut n: i128) -> Self {
+        let mut digits = Vec::new();
+        while n != 0 {
+            // Euclidean division keeps this clear of overflow at i128::MIN
+            let (mut q, mut r) = (n.div_euclid(5), n.rem_euclid(5));
+            if r > 2 {
+                r -= 5;
+                q += 1;
+            }
+            digits.push(r as i8);
+            n = q;
+        }
+        Self { digits }
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(n: i64) -> Self {
+        i128::from(n).into()
+    }
+}
+
+impl TryFrom<&Snafu> for i128 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &Snafu) -> std::result::Result<Self, Self::Error> {
+        value.digits.iter().rev().try_fold(0i128, |n, &d| {
+            let d = i128::from(d);
+            // When the digit pulls back towards zero, step the prefix towards
+            // zero first so `5 * n` can't overflow on the way to a value that
+            // fits, as at i128::MIN
+            let s = if n.signum() == -d.signum() {
+                d.signum()
+            } else {
+                0
+            };
+            (n + s)
+                .checked_mul(5)
+                .and_then(|n| n.checked_add(d - 5 * s))
+                .ok_or_else(|| anyhow::anyhow!("{value} does not fit in an i128"))
+        })
+    }
+}
+
+impl TryFrom<&Snafu> for i64 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &Snafu) -> std::result::Result<Self, Self::Error> {
+        let n = i128::try_from(value)?;
+        n.try_into()
+            .map_err(|_| anyhow::anyhow!("{value} does not fit in an i64"))
+    }
+}
+
+/// Column addition, carrying -1, 0 or 1 into the next digit.
+impl Add for &Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Snafu {
+        let len = self.digits.len().max(rhs.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+        for i in 0..len {
+            let a = self.digits.get(i).copied().unwrap_or(0);
+            let b = rhs.digits.get(i).copied().unwrap_or(0);
+            let mut d = a + b + carry;
+            carry = 0;
+            if d > 2 {
+                d -= 5;
+                carry = 1;
+            } else if d < -2 {
+                d += 5;
+                carry = -1;
+            }
+            digits.push(d);
+        }
+        digits.push(carry);
+        Snafu::from_digits(digits)
+    }
+}
+
+impl Add for Snafu {
+    type Output = Snafu;
+
+    fn add(self, rhs: Self) -> Snafu {
+        &self + &rhs
+    }
+}
+
+impl<'a> Sum<&'a Snafu> for Snafu {
+    fn sum<I: Iterator<Item = &'a Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |total, n| &total + n)
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Snafu>>(iter: I) -> Self {
+        iter.fold(Snafu::default(), |total, n| &total + &n)
+    }
+}
+
+pub fn parse(data: &str) -> Result<Vec<Snafu>> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// The fuel every hot air balloon needs, added up in SNAFU.
+pub fn part1(data: &str) -> Result<String> {
+    Ok(parse(data)?.into_iter().sum::<Snafu>().to_string())
+}
+
+/// The last star comes from having all the others.
+pub fn part2(data: &str) -> Result<String> {
+    parse(data)?;
+    Ok("Start the blender".to_string())
+}
+
+#[test]
+fn test_snafu() -> Result<()> {
+    for (decimal, snafu) in [
+        (0i64, "0"),
+        (1, "1"),
+        (3, "1="),
+        (8, "2="),
+        (10, "20"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+        (-3, "-2"),
+    ] {
+        assert_eq!(Snafu::from(decimal).to_string(), snafu);
+        assert_eq!(i64::try_from(&snafu.parse::<Snafu>()?)?, decimal);
+    }
+    assert_eq!("00121".parse::<Snafu>()?, Snafu::from(36i64));
+    assert_eq!(format!("{:>4}", Snafu::from(3i64)), "  1=");
+
+    let err = "1=3".parse::<Snafu>().unwrap_err().to_string();
+    assert_eq!(err, "Unexpected `3` at column 3 of `1=3`");
+    assert!("".parse::<Snafu>().is_err());
+
+    let huge: Snafu = "2".repeat(60).parse()?;
+    assert!(i128::try_from(&huge).is_err());
+    assert!(i64::try_from(&Snafu::from(i128::from(i64::MAX) + 1)).is_err());
+    Ok(())
+}
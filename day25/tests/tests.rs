@@ -0,0 +1,78 @@
+use anyhow::Result;
+use aocday25::Snafu;
+use proptest::prelude::*;
+
+#[test]
+#[allow(unused)]
+fn test_part1() -> Result<()> {
+    let expected = "2=-1=0".to_string();
+    let got = aocday25::part1(aocday25::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+#[allow(unused)]
+fn test_part2() -> Result<()> {
+    let expected = "Start the blender".to_string();
+    let got = aocday25::part2(aocday25::data::sample_data()?.as_str())?;
+
+    assert_eq!(expected, got);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_small() -> Result<()> {
+    for n in -100_000i64..=100_000 {
+        let text = Snafu::from(n).to_string();
+        assert_eq!(i64::try_from(&text.parse::<Snafu>()?)?, n, "{text}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_round_trip_limits() -> Result<()> {
+    for n in [i128::MIN, i128::MIN + 1, i128::MAX, i128::MAX - 1] {
+        let snafu: Snafu = Snafu::from(n).to_string().parse()?;
+        assert_eq!(i128::try_from(&snafu)?, n);
+    }
+    for n in [i64::MIN, i64::MAX] {
+        let snafu: Snafu = Snafu::from(n).to_string().parse()?;
+        assert_eq!(i64::try_from(&snafu)?, n);
+        assert_eq!(i128::try_from(&snafu)?, i128::from(n));
+    }
+    let past_max = Snafu::from(i128::MAX) + Snafu::from(1i64);
+    assert!(i128::try_from(&past_max).is_err());
+    let past_min = Snafu::from(i128::MIN) + Snafu::from(-1i64);
+    assert!(i128::try_from(&past_min).is_err());
+    assert!(i64::try_from(&Snafu::from(i128::from(i64::MIN) - 1)).is_err());
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn round_trip_i64(n: i64) {
+        let snafu = Snafu::from(n);
+        prop_assert_eq!(i64::try_from(&snafu).unwrap(), n);
+        prop_assert_eq!(snafu.to_string().parse::<Snafu>().unwrap(), snafu);
+    }
+
+    #[test]
+    fn round_trip_i128(n: i128) {
+        let snafu = Snafu::from(n);
+        prop_assert_eq!(i128::try_from(&snafu).unwrap(), n);
+        prop_assert_eq!(snafu.to_string().parse::<Snafu>().unwrap(), snafu);
+    }
+
+    #[test]
+    fn addition_matches_i128(a: i64, b: i64) {
+        let sum = Snafu::from(a) + Snafu::from(b);
+        prop_assert_eq!(i128::try_from(&sum).unwrap(), i128::from(a) + i128::from(b));
+    }
+
+    #[test]
+    fn digits_round_trip(text in "[12][=\\-012]{0,80}") {
+        prop_assert_eq!(text.parse::<Snafu>().unwrap().to_string(), text);
+    }
+}
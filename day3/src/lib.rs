@@ -145,11 +145,21 @@ fn common_item(three: [Rucksack;3]) -> Item {
     common.to_owned()
 }
 
-pub fn part2(filename: &str) -> Result<String> {
-    let data = std::fs::read_to_string(filename)?;
-    let lines = data.lines().into_iter();
-    let rucksacks = lines.map(|l| l.try_into().unwrap());
-    let groups = groups_of_three(rucksacks);
+pub fn part1(data: &str) -> Result<String> {
+    let rucksacks: Result<Vec<Rucksack>> = data.lines().map(|line| line.try_into()).collect();
+    let mut sum = 0u64;
+    for rs in rucksacks? {
+        let item = rs
+            .in_both()
+            .ok_or_else(|| anyhow::anyhow!("No item in both compartments"))?;
+        sum += item.priority as u64;
+    }
+    Ok(format!("{sum}"))
+}
+
+pub fn part2(data: &str) -> Result<String> {
+    let rucksacks: Result<Vec<Rucksack>> = data.lines().map(|line| line.try_into()).collect();
+    let groups = groups_of_three(rucksacks?.into_iter());
 
     let missing = groups.map(common_item);
 
@@ -159,7 +169,7 @@ pub fn part2(filename: &str) -> Result<String> {
 
 #[test]
 fn test_grouping() -> Result<()> {
-    let sum = part2("sample.txt")?;
+    let sum = part2(include_str!("../sample.txt"))?;
 
     assert_eq!(sum,"70");
 
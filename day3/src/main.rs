@@ -2,7 +2,7 @@ use anyhow::Result;
 use day3::{Rucksack, part2};
 
 fn main() -> Result<()> {
-    let data = include_str!("../problem.txt");
+    let data = &puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))?;
     let rucksacks: Result<Vec<Rucksack>> = data.lines().map(|line| line.try_into()).collect();
     let rucksacks = rucksacks?;
 
@@ -13,7 +13,7 @@ fn main() -> Result<()> {
 
     println!("{sum}");
 
-    println!("Part2: {}",part2("problem.txt")?);
+    println!("Part2: {}",part2(data)?);
 
     Ok(())
 }
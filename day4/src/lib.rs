@@ -1,91 +1,53 @@
 use std::ops::RangeInclusive;
 
 use anyhow::Result;
+use line_format::LineFormat;
 
+#[derive(LineFormat)]
+#[line_format("{elf1},{elf2}")]
 pub struct ElfPair {
     elf1: ElfRange,
     elf2: ElfRange,
 }
-impl TryFrom<&str> for ElfPair {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut pairs = value.split(',');
-        Ok(Self {
-            elf1: pairs
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("Could not get elf 1"))?
-                .try_into()?,
-            elf2: pairs
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("Could not get elf 1"))?
-                .try_into()?,
-        })
-    }
-}
 impl ElfPair {
     pub fn fully_overlaps(&self) -> bool {
-        self.elf1
-            .range
-            .clone()
-            .all(|v| self.elf2.range.contains(&v))
-            || self
-                .elf2
-                .range
-                .clone()
-                .all(|v| self.elf1.range.contains(&v))
+        self.elf1.range().all(|v| self.elf2.range().contains(&v))
+            || self.elf2.range().all(|v| self.elf1.range().contains(&v))
     }
     pub fn overlaps_at_all(&self) -> bool {
-        self.elf1
-            .range
-            .clone()
-            .any(|v| self.elf2.range.contains(&v))
-            || self
-                .elf2
-                .range
-                .clone()
-                .any(|v| self.elf1.range.contains(&v))
+        self.elf1.range().any(|v| self.elf2.range().contains(&v))
+            || self.elf2.range().any(|v| self.elf1.range().contains(&v))
     }
 }
 
+#[derive(LineFormat)]
+#[line_format("{from}-{to}")]
 pub struct ElfRange {
-    pub range: RangeInclusive<u32>,
+    pub from: u32,
+    pub to: u32,
 }
-impl TryFrom<&str> for ElfRange {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut value = value.split("-");
-        let from = value
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("No from value"))?
-            .parse()?;
-        let to = value
-            .next()
-            .ok_or_else(|| anyhow::anyhow!("No to value"))?
-            .parse()?;
-
-        let range = from..=to;
-        Ok(ElfRange { range })
+impl ElfRange {
+    pub fn range(&self) -> RangeInclusive<u32> {
+        self.from..=self.to
     }
 }
 
 #[test]
 fn test_import_elfrange() {
     let elf: ElfRange = "2-4".try_into().unwrap();
-    assert_eq!(elf.range, 2..=4);
-    assert_eq!(elf.range.count(),3);
+    assert_eq!(elf.range(), 2..=4);
+    assert_eq!(elf.range().count(),3);
 }
 
 #[test]
 fn test_import_elfpair() {
     let pair: ElfPair = "2-4,6-8".try_into().unwrap();
-    assert_eq!(pair.elf1.range, 2..=4);
-    assert_eq!(pair.elf2.range, 6..=8);
+    assert_eq!(pair.elf1.range(), 2..=4);
+    assert_eq!(pair.elf2.range(), 6..=8);
 }
 
 #[allow(unused)]
-fn part1(data: &str) -> Result<String> {
+pub fn part1(data: &str) -> Result<String> {
     let pairs: Result<Vec<ElfPair>> = data.lines().map(|l| l.try_into()).collect();
     let pairs = pairs?;
     Ok(pairs
@@ -96,7 +58,7 @@ fn part1(data: &str) -> Result<String> {
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
+pub fn part2(data: &str) -> Result<String> {
     let pairs: Result<Vec<ElfPair>> = data.lines().map(|l| l.try_into()).collect();
     let pairs = pairs?;
     Ok(pairs
@@ -112,11 +74,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
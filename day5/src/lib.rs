@@ -1,33 +1,22 @@
 use anyhow::Result;
+use line_format::LineFormat;
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug, LineFormat)]
+#[line_format("move {count} from {from} to {to}")]
 pub struct Command {
     pub count: usize,
+    #[line_format(with = stack_index)]
     pub from: usize,
+    #[line_format(with = stack_index)]
     pub to: usize,
 }
-impl TryFrom<&str> for Command {
-    type Error = anyhow::Error;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut value = value.split(' ');
-        let mut get = move || {
-            value
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("Couldn't get next in command"))
-        };
-        let _move = get()?;
-        let count = get()?.parse()?;
-        let _from = get()?;
-        let from: usize = get()?.parse()?;
-        let _to = get()?;
-        let to: usize = get()?.parse()?;
-        Ok(Self {
-            count,
-            from: from - 1,
-            to: to - 1,
-        })
-    }
+/// Stacks are numbered from 1 in the input but indexed from 0.
+fn stack_index(value: &str) -> Result<usize> {
+    let number: usize = value.parse()?;
+    number
+        .checked_sub(1)
+        .ok_or_else(|| anyhow::anyhow!("Stack numbers start at 1"))
 }
 
 #[test]
@@ -36,6 +25,9 @@ fn test_command() -> Result<()> {
     assert_eq!(c.count, 1);
     assert_eq!(c.from, 1);
     assert_eq!(c.to, 2);
+
+    assert!(Command::try_from("move 1 from 0 to 3").is_err());
+    assert!(Command::try_from("move 1 from 2").is_err());
     Ok(())
 }
 
@@ -211,32 +203,80 @@ fn test_stack_reader() -> Result<()> {
     Ok(())
 }
 
-#[allow(unused)]
-fn part1(data: &str) -> Result<String> {
-    let data = split_stack_commands(data)?;
-    let mut stacks: Stacks = data.0.try_into()?;
+#[derive(Clone, Copy, Debug)]
+pub enum CrateMover {
+    Model9000,
+    Model9001,
+}
+
+/// The rearrangement procedure, run one command at a time.
+#[derive(Clone, Debug)]
+pub struct Crane {
+    pub stacks: Stacks,
+    pub commands: Vec<Command>,
+    pub next: usize,
+    pub model: CrateMover,
+}
+impl Crane {
+    pub fn new(data: &str, model: CrateMover) -> Result<Self> {
+        let (stacks, commands) = split_stack_commands(data)?;
+        let commands = commands
+            .into_iter()
+            .map(Command::try_from)
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            stacks: stacks.try_into()?,
+            commands,
+            next: 0,
+            model,
+        })
+    }
 
-    for c in data.1 {
-        let c: Command = c.try_into()?;
-        stacks.execute(&c);
+    /// Executes the next command and returns it, or `None` once all have run.
+    pub fn step(&mut self) -> Result<Option<&Command>> {
+        let command = match self.commands.get(self.next) {
+            Some(command) => command,
+            None => return Ok(None),
+        };
+        match self.model {
+            CrateMover::Model9000 => self.stacks.execute(command)?,
+            CrateMover::Model9001 => self.stacks.execute_9001(command)?,
+        }
+        self.next += 1;
+        Ok(Some(command))
     }
 
-    println!("{stacks:?}");
+    pub fn run(&mut self) -> Result<()> {
+        while self.step()?.is_some() {}
+        Ok(())
+    }
+}
 
-    Ok(stacks.across())
+#[test]
+fn test_crane_step() -> Result<()> {
+    let mut crane = Crane::new(&sample_data()?, CrateMover::Model9000)?;
+    let command = crane.step()?.expect("first command");
+    assert_eq!(command.count, 1);
+    assert_eq!(crane.stacks.across(), "DCP");
+    crane.run()?;
+    assert!(crane.step()?.is_none());
+    assert_eq!(crane.next, 4);
+    Ok(())
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
-    let data = split_stack_commands(data)?;
-    let mut stacks: Stacks = data.0.try_into()?;
+pub fn part1(data: &str) -> Result<String> {
+    let mut crane = Crane::new(data, CrateMover::Model9000)?;
+    crane.run()?;
+    Ok(crane.stacks.across())
+}
 
-    for c in data.1 {
-        let c: Command = c.try_into()?;
-        stacks.execute_9001(&c);
-    }
+#[allow(unused)]
+pub fn part2(data: &str) -> Result<String> {
+    let mut crane = Crane::new(data, CrateMover::Model9001)?;
+    crane.run()?;
 
-    Ok(stacks.across())
+    Ok(crane.stacks.across())
 }
 
 #[cfg(test)]
@@ -245,11 +285,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
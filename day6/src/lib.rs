@@ -24,12 +24,12 @@ pub fn get_window(data: &str, window_size: usize) -> Result<usize> {
 }
 
 #[allow(unused)]
-fn part1(data: &str) -> Result<String> {
+pub fn part1(data: &str) -> Result<String> {
     Ok(get_sof(data)?.to_string())
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
+pub fn part2(data: &str) -> Result<String> {
     Ok(get_som(data)?.to_string())
 }
 
@@ -39,11 +39,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
@@ -193,7 +193,7 @@ impl Dir {
 }
 
 #[allow(unused)]
-fn part1(data: &str) -> Result<String> {
+pub fn part1(data: &str) -> Result<String> {
     let data = data.lines().map(|l| l.to_owned()).into_iter();
     let mut data = data.peekable();
 
@@ -211,7 +211,7 @@ fn part1(data: &str) -> Result<String> {
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
+pub fn part2(data: &str) -> Result<String> {
     let data = data.lines().map(|l| l.to_owned()).into_iter();
     let mut data = data.peekable();
 
@@ -246,11 +246,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
@@ -1,17 +1,18 @@
 use std::num::ParseIntError;
 
 use anyhow::Result;
+use geometry::{Dir4, Point};
 
 #[derive(Debug)]
 struct Grid {
     trees: Vec<Vec<u64>>,
 }
 impl Grid {
-    fn all_points(&self) -> impl Iterator<Item = (usize, usize)> {
+    fn all_points(&self) -> impl Iterator<Item = Point<usize>> {
         let mut points = Vec::new();
-        for y in 0..self.trees.len() {
-            for x in 0..self.trees.len() {
-                points.push((x, y))
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                points.push(Point::new(x, y))
             }
         }
         points.into_iter()
@@ -22,45 +23,37 @@ impl Grid {
     fn height(&self) -> usize {
         self.trees.len()
     }
-    fn is_visible(&self, point: (usize, usize)) -> Result<bool> {
-        let myheight = *self
+    fn height_at(&self, point: Point<usize>) -> Result<u64> {
+        Ok(*self
             .trees
-            .get(point.1)
+            .get(point.y)
             .ok_or_else(|| anyhow::anyhow!("Could not get y point"))?
-            .get(point.0)
-            .ok_or_else(|| anyhow::anyhow!("Could not get x point"))?;
-        let left = (0..point.0).map(|x| self.trees[point.1][x]);
-        let right = (point.0 + 1..self.width()).map(|x| self.trees[point.1][x]);
-        let up = (0..point.1).map(|y| self.trees[y][point.0]);
-        let down = (point.1 + 1..self.height()).map(|y| self.trees[y][point.0]);
-
-        if is_visible(left, myheight)
-            || is_visible(right, myheight)
-            || is_visible(up, myheight)
-            || is_visible(down, myheight)
-        {
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+            .get(point.x)
+            .ok_or_else(|| anyhow::anyhow!("Could not get x point"))?)
+    }
+    /// Tree heights walking out from `point` (exclusive) to the edge.
+    fn ray(&self, point: Point<usize>, dir: Dir4) -> impl Iterator<Item = u64> + '_ {
+        let (width, height) = (self.width(), self.height());
+        std::iter::successors(Some(point), move |p| p.checked_add(dir.vector()))
+            .skip(1)
+            .take_while(move |p| p.in_bounds(width, height))
+            .map(|p| self.trees[p.y][p.x])
     }
+    fn is_visible(&self, point: Point<usize>) -> Result<bool> {
+        let myheight = self.height_at(point)?;
 
-    fn house_score(&self, point: (usize, usize)) -> Result<usize> {
-        let myheight = *self
-            .trees
-            .get(point.1)
-            .ok_or_else(|| anyhow::anyhow!("Could not get y point"))?
-            .get(point.0)
-            .ok_or_else(|| anyhow::anyhow!("Could not get x point"))?;
-        let left = (0..point.0).map(|x| self.trees[point.1][x]).rev();
-        let right = (point.0 + 1..self.width()).map(|x| self.trees[point.1][x]);
-        let up = (0..point.1).map(|y| self.trees[y][point.0]).rev();
-        let down = (point.1 + 1..self.height()).map(|y| self.trees[y][point.0]);
-
-        Ok(visible_trees(left, myheight)
-            * visible_trees(right, myheight)
-            * visible_trees(up, myheight)
-            * visible_trees(down, myheight))
+        Ok(Dir4::ALL
+            .into_iter()
+            .any(|dir| is_visible(self.ray(point, dir), myheight)))
+    }
+
+    fn house_score(&self, point: Point<usize>) -> Result<usize> {
+        let myheight = self.height_at(point)?;
+
+        Ok(Dir4::ALL
+            .into_iter()
+            .map(|dir| visible_trees(self.ray(point, dir), myheight))
+            .product())
     }
 }
 
@@ -95,11 +88,9 @@ impl TryFrom<&str> for Grid {
 }
 
 #[allow(unused)]
-fn part1(data: &str) -> Result<String> {
+pub fn part1(data: &str) -> Result<String> {
     let trees: Grid = data.try_into()?;
 
-    println!("{trees:?}");
-
     let mut accum = 0usize;
     for point in trees.all_points() {
         if trees.is_visible(point)? {
@@ -111,13 +102,9 @@ fn part1(data: &str) -> Result<String> {
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
+pub fn part2(data: &str) -> Result<String> {
     let trees: Grid = data.try_into()?;
 
-    println!("{:?}",trees.all_points().map(|p| trees.house_score(p)).collect::<Vec<_>>());
-
-    _ = trees.house_score((2,1));
-
     let mut max_score = None;
     for point in trees.all_points() {
         let score = trees.house_score(point)?;
@@ -134,11 +121,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
@@ -2,61 +2,41 @@
 use std::collections::HashSet;
 
 use anyhow::Result;
+use geometry::{Dir4, Point, Vector};
+use line_format::LineFormat;
 
+#[derive(LineFormat)]
+#[line_format("{dir} {count}")]
 struct Command {
     dir: Dir,
     count: usize,
 }
 impl Command {
-    fn move_dir(&self) -> (i64, i64) {
+    fn move_dir(&self) -> Vector<i64> {
         self.dir.move_dir()
     }
 }
-impl TryFrom<&str> for Command {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut value = value.split(" ");
-        Ok(Command {
-            dir: value
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("Could not get dir"))?
-                .try_into()?,
-            count: value
-                .next()
-                .ok_or_else(|| anyhow::anyhow!("COuld not get count"))?
-                .parse()?,
-        })
-    }
-}
 
+#[derive(LineFormat)]
 enum Dir {
+    #[line_format("L")]
     L,
+    #[line_format("R")]
     R,
+    #[line_format("U")]
     U,
+    #[line_format("D")]
     D,
 }
 impl Dir {
-    fn move_dir(&self) -> (i64, i64) {
+    fn move_dir(&self) -> Vector<i64> {
         match self {
-            Dir::L => (-1, 0),
-            Dir::R => (1, 0),
-            Dir::U => (0, 1),
-            Dir::D => (0, -1),
+            Dir::L => Dir4::Left,
+            Dir::R => Dir4::Right,
+            Dir::U => Dir4::Up,
+            Dir::D => Dir4::Down,
         }
-    }
-}
-impl TryFrom<&str> for Dir {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(match value {
-            "L" => Dir::L,
-            "R" => Dir::R,
-            "U" => Dir::U,
-            "D" => Dir::D,
-            _ => anyhow::bail!("Unknown direction {value}"),
-        })
+        .vector()
     }
 }
 
@@ -68,96 +48,96 @@ fn test_command() -> Result<()> {
     Ok(())
 }
 
-#[allow(unused)]
-fn part1(data: &str) -> Result<String> {
-    let mut headpos = (0i64, 0i64);
-    let mut tailpos = (0i64, 0i64);
-
-    let mut positions = HashSet::new();
-    positions.insert(tailpos);
-
-    for line in data.lines() {
-        let c: Command = line.try_into()?;
-        for _ in 0..c.count {
-            let prior_head = headpos;
-
-            let movedir = c.move_dir();
-            headpos = (headpos.0 + movedir.0, headpos.1 + movedir.1);
-
-            let xdist = (headpos.0 - tailpos.0).abs();
-            let ydist = (headpos.1 - tailpos.1).abs();
-            let dist = xdist + ydist;
-            if xdist <= 1 && ydist <= 1 {
-                // adjacent, don't do anything
-            } else {
-                tailpos = prior_head
+/// A rope whose head follows the commands, dragging the other knots behind.
+pub struct Rope {
+    pub knots: Vec<Point<i64>>,
+    pub visited: HashSet<Point<i64>>,
+    commands: Vec<Command>,
+    command: usize,
+    moved: usize,
+}
+impl Rope {
+    pub fn new(data: &str, knots: usize) -> Result<Self> {
+        if knots == 0 {
+            anyhow::bail!("A rope needs at least one knot");
+        }
+        let commands = data
+            .lines()
+            .map(Command::try_from)
+            .collect::<Result<_>>()?;
+        let knots = vec![Point::new(0i64, 0i64); knots];
+        let visited = knots.last().copied().into_iter().collect();
+        Ok(Self {
+            knots,
+            visited,
+            commands,
+            command: 0,
+            moved: 0,
+        })
+    }
+
+    /// Moves the head a single square and propagates the move down the rope.
+    /// Returns false once every command has been followed.
+    pub fn step(&mut self) -> bool {
+        let c = loop {
+            match self.commands.get(self.command) {
+                None => return false,
+                Some(c) if self.moved < c.count => break c,
+                Some(_) => {
+                    self.command += 1;
+                    self.moved = 0;
+                }
             }
-            positions.insert(tailpos);
+        };
+        self.moved += 1;
+
+        self.knots[0] += c.move_dir();
+
+        // Proprogate the move down the line
+        for i in 1..self.knots.len() {
+            let toward = self.knots[i - 1] - self.knots[i];
+            if toward.chebyshev_len() <= 1 {
+                // adjacent, and so is everything behind us
+                break;
+            }
+            self.knots[i] += toward.signum();
         }
+        self.visited.insert(*self.knots.last().unwrap());
+        true
     }
 
-    Ok(positions.len().to_string())
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
 }
 
-fn distance(headpos: (i64, i64), tailpos: (i64, i64)) -> u8 {
-    if headpos == tailpos {
-        0
-    } else if headpos.0 == tailpos.0 || headpos.1 == tailpos.1 {
-        1
-    } else {
-        2
-    }
+#[allow(unused)]
+pub fn part1(data: &str) -> Result<String> {
+    let mut rope = Rope::new(data, 2)?;
+    rope.run();
+
+    Ok(rope.visited.len().to_string())
 }
 
 #[allow(unused)]
-fn part2(data: &str) -> Result<String> {
-    let mut rope = vec![(0i64, 0i64); 10];
-
-    let mut positions = HashSet::new();
-    positions.insert(rope.last().unwrap().to_owned());
-
-    for line in data.lines() {
-        let c: Command = line.try_into()?;
-        for _ in 0..c.count {
-            // Move the first position
-            let mut adj_pos = {
-                let first = rope.first_mut().unwrap();
-
-                let movedir = c.move_dir();
-                *first = (first.0 + movedir.0, first.1 + movedir.1);
-
-                *first
-            };
-
-            // Proprogate the move down the line
-            for i in 1..rope.len() {
-                let me = rope.get_mut(i).unwrap();
-
-                let xdist = (adj_pos.0 - me.0).abs();
-                let ydist = (adj_pos.1 - me.1).abs();
-                let dist = xdist + ydist;
-                if xdist <= 1 && ydist <= 1 {
-                    // adjacent, don't do anything
-                } else {
-                    *me = (me.0 + clamp11(adj_pos.0 - me.0), me.1 + clamp11(adj_pos.1 - me.1));
-                }
-                adj_pos = *me;
-            }
-            positions.insert(rope.last().unwrap().to_owned());
-        }
-    }
+pub fn part2(data: &str) -> Result<String> {
+    let mut rope = Rope::new(data, 10)?;
+    rope.run();
 
-    Ok(positions.len().to_string())
+    Ok(rope.visited.len().to_string())
 }
 
-fn clamp11(value: i64) -> i64 {
-    if value > 1 {
-        1
-    } else if value < -1 {
-        -1
-    } else {
-        value
-    }
+#[test]
+fn test_rope_step() -> Result<()> {
+    let mut rope = Rope::new("R 2\nU 1", 2)?;
+    assert!(rope.step());
+    assert_eq!(rope.knots, vec![Point::new(1, 0), Point::new(0, 0)]);
+    assert!(rope.step());
+    assert_eq!(rope.knots, vec![Point::new(2, 0), Point::new(1, 0)]);
+    assert!(rope.step());
+    assert!(!rope.step());
+    assert_eq!(rope.visited.len(), 2);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -166,11 +146,11 @@ fn sample_data() -> Result<String> {
 }
 
 fn part1_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 fn part2_problem_data() -> Result<String> {
-    Ok(std::fs::read_to_string("problem.txt")?)
+    puzzle_input::read("problem.txt")
 }
 
 pub fn part1_main() -> Result<String> {
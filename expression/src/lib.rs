@@ -0,0 +1,324 @@
+//! Integer arithmetic expressions such as `old * 19` or `(a + 2) / b`.
+//!
+//! Evaluation is exact: overflow and division with a remainder are errors
+//! rather than silently wrong answers.  [`Expr::solve`] runs an expression
+//! backwards to find the variable that makes it come out to a given value.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+impl Op {
+    pub fn apply(self, a: i128, b: i128) -> Result<i128> {
+        let value = match self {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
+            Op::Div => return exact_div(a, b),
+        };
+        value.ok_or_else(|| anyhow!("{a} {self} {b} overflows"))
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Op::Add | Op::Sub => 1,
+            Op::Mul | Op::Div => 2,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+        };
+        f.write_str(symbol)
+    }
+}
+
+fn exact_div(a: i128, b: i128) -> Result<i128> {
+    if b == 0 {
+        bail!("{a} / 0 is undefined");
+    }
+    if a % b != 0 {
+        bail!("{a} / {b} is not a whole number");
+    }
+    a.checked_div(b).ok_or_else(|| anyhow!("{a} / {b} overflows"))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Num(i128),
+    Var(String),
+    Binary(Box<Expr>, Op, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(left: Expr, op: Op, right: Expr) -> Self {
+        Expr::Binary(Box::new(left), op, Box::new(right))
+    }
+
+    /// Evaluates with `vars` giving the value of each variable.
+    pub fn eval(&self, vars: &impl Fn(&str) -> Option<i128>) -> Result<i128> {
+        match self {
+            Expr::Num(n) => Ok(*n),
+            Expr::Var(name) => vars(name).ok_or_else(|| anyhow!("No value for {name}")),
+            Expr::Binary(left, op, right) => op.apply(left.eval(vars)?, right.eval(vars)?),
+        }
+    }
+
+    pub fn contains(&self, var: &str) -> bool {
+        match self {
+            Expr::Num(_) => false,
+            Expr::Var(name) => name == var,
+            Expr::Binary(left, _, right) => left.contains(var) || right.contains(var),
+        }
+    }
+
+    /// Replaces variables with the expression `with` gives for them, if any,
+    /// recursively.
+    pub fn substitute(&self, with: &impl Fn(&str) -> Option<Result<Expr>>) -> Result<Expr> {
+        Ok(match self {
+            Expr::Num(_) => self.clone(),
+            Expr::Var(name) => match with(name) {
+                Some(expr) => expr?.substitute(with)?,
+                None => self.clone(),
+            },
+            Expr::Binary(left, op, right) => {
+                Expr::binary(left.substitute(with)?, *op, right.substitute(with)?)
+            }
+        })
+    }
+
+    /// The value of `var` that makes the expression equal `target`, undoing
+    /// each operation on the way down to it.  `var` must appear exactly once.
+    pub fn solve(
+        &self,
+        var: &str,
+        target: i128,
+        vars: &impl Fn(&str) -> Option<i128>,
+    ) -> Result<i128> {
+        match self {
+            Expr::Var(name) if name == var => Ok(target),
+            Expr::Binary(left, op, right) => match (left.contains(var), right.contains(var)) {
+                (true, true) => bail!("{var} appears on both sides of {self}"),
+                (true, false) => {
+                    let r = right.eval(vars)?;
+                    // target = x op r
+                    let x = match op {
+                        Op::Add => Op::Sub.apply(target, r)?,
+                        Op::Sub => Op::Add.apply(target, r)?,
+                        Op::Mul => Op::Div.apply(target, r)?,
+                        Op::Div => Op::Mul.apply(target, r)?,
+                    };
+                    left.solve(var, x, vars)
+                }
+                (false, true) => {
+                    let l = left.eval(vars)?;
+                    // target = l op x
+                    let x = match op {
+                        Op::Add => Op::Sub.apply(target, l)?,
+                        Op::Sub => Op::Sub.apply(l, target)?,
+                        Op::Mul => Op::Div.apply(target, l)?,
+                        Op::Div => Op::Div.apply(l, target)?,
+                    };
+                    right.solve(var, x, vars)
+                }
+                (false, false) => bail!("{var} does not appear in {self}"),
+            },
+            _ => bail!("{var} does not appear in {self}"),
+        }
+    }
+
+    /// Parses the usual infix syntax, `*` and `/` binding tighter than `+`
+    /// and `-`, all left associative.
+    pub fn parse(input: &str) -> Result<Expr> {
+        let mut parser = Parser { input, pos: 0 };
+        let expr = parser.expr(0)?;
+        parser.skip_spaces();
+        if parser.pos < input.len() {
+            return Err(parser.error("an operator"));
+        }
+        Ok(expr)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{n}"),
+            Expr::Var(name) => f.write_str(name),
+            Expr::Binary(left, op, right) => write!(f, "({left} {op} {right})"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Expr {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        Expr::parse(value)
+    }
+}
+
+impl std::str::FromStr for Expr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Expr::parse(s)
+    }
+}
+
+/// Precedence climbing over the input, one byte at a time.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn op(&mut self) -> Option<Op> {
+        self.skip_spaces();
+        match self.peek()? {
+            b'+' => Some(Op::Add),
+            b'-' => Some(Op::Sub),
+            b'*' => Some(Op::Mul),
+            b'/' => Some(Op::Div),
+            _ => None,
+        }
+    }
+
+    fn expr(&mut self, min_precedence: u8) -> Result<Expr> {
+        let mut left = self.atom()?;
+        while let Some(op) = self.op().filter(|op| op.precedence() > min_precedence) {
+            self.pos += 1;
+            let right = self.expr(op.precedence())?;
+            left = Expr::binary(left, op, right);
+        }
+        Ok(left)
+    }
+
+    fn atom(&mut self) -> Result<Expr> {
+        self.skip_spaces();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let expr = self.expr(0)?;
+                self.skip_spaces();
+                if self.peek() != Some(b')') {
+                    return Err(self.error("`)`"));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(b'0'..=b'9') => {
+                while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+                    self.pos += 1;
+                }
+                let digits = &self.input[start..self.pos];
+                digits.parse().map(Expr::Num).map_err(|e| {
+                    anyhow!(
+                        "could not parse `{digits}` at column {} of `{}`: {e}",
+                        start + 1,
+                        self.input
+                    )
+                })
+            }
+            Some(b) if b.is_ascii_alphabetic() || b == b'_' => {
+                while self
+                    .peek()
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_')
+                {
+                    self.pos += 1;
+                }
+                Ok(Expr::Var(self.input[start..self.pos].to_string()))
+            }
+            _ => Err(self.error("a number, a name or `(`")),
+        }
+    }
+
+    fn error(&self, expected: &str) -> anyhow::Error {
+        let found = match self.input[self.pos..].chars().next() {
+            Some(c) => format!("`{c}`"),
+            None => "the end".to_string(),
+        };
+        anyhow!(
+            "expected {expected} but found {found} at column {} of `{}`",
+            self.pos + 1,
+            self.input
+        )
+    }
+}
+
+#[cfg(test)]
+fn no_vars(_: &str) -> Option<i128> {
+    None
+}
+
+#[test]
+fn test_parse() -> Result<()> {
+    let expr: Expr = "1 + 2 * 3 - 4 / (1 + 1)".parse()?;
+    assert_eq!(expr.to_string(), "((1 + (2 * 3)) - (4 / (1 + 1)))");
+    assert_eq!(expr.eval(&no_vars)?, 5);
+    assert_eq!(Expr::parse("10 - 3 - 2")?.eval(&no_vars)?, 5);
+
+    let expr = Expr::parse("old * old")?;
+    assert_eq!(expr.eval(&|name| (name == "old").then_some(7))?, 49);
+    assert!(expr.eval(&no_vars).is_err());
+
+    let err = Expr::parse("1 + * 2").unwrap_err().to_string();
+    assert_eq!(err, "expected a number, a name or `(` but found `*` at column 5 of `1 + * 2`");
+    let err = Expr::parse("(1 + 2").unwrap_err().to_string();
+    assert_eq!(err, "expected `)` but found the end at column 7 of `(1 + 2`");
+    assert!(Expr::parse("1 2").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_exact() {
+    assert!(Expr::parse("7 / 2").unwrap().eval(&no_vars).is_err());
+    assert!(Expr::parse("7 / 0").unwrap().eval(&no_vars).is_err());
+    let big = Expr::binary(Expr::Num(i128::MAX), Op::Add, Expr::Num(1));
+    assert!(big.eval(&no_vars).is_err());
+}
+
+#[test]
+fn test_solve() -> Result<()> {
+    for (text, x) in [
+        ("(x + 3) * 2", 5),
+        ("100 - x * 4", 7),
+        ("120 / (x - 1)", 13),
+        ("(x / 3) + y", 9),
+    ] {
+        let expr = Expr::parse(text)?;
+        let vars = |name: &str| match name {
+            "x" => Some(x),
+            "y" => Some(4),
+            _ => None,
+        };
+        let target = expr.eval(&vars)?;
+        assert_eq!(expr.solve("x", target, &|name| (name == "y").then_some(4))?, x);
+    }
+    assert!(Expr::parse("x * x")?.solve("x", 4, &no_vars).is_err());
+    assert!(Expr::parse("y + 1")?.solve("x", 4, &no_vars).is_err());
+    Ok(())
+}
@@ -0,0 +1,44 @@
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Solves `part` (1 or 2) of `day` for the UTF-8 puzzle input of `len` bytes
+ * at `input_ptr`.
+ *
+ * Returns a NUL terminated answer that the caller owns and must release with
+ * `aoc_free_string`.  Returns NULL on failure, after which `aoc_last_error`
+ * explains why.
+ *
+ * # Safety
+ *
+ * `input_ptr` must point to `len` readable bytes.  It may be NULL when `len`
+ * is 0.
+ */
+char *aoc_solve(uint8_t day, uint8_t part, const uint8_t *input_ptr, size_t len);
+
+/**
+ * The reason the last `aoc_solve` on this thread failed, or NULL if it
+ * succeeded.  The caller owns the string and must release it with
+ * `aoc_free_string`.
+ */
+char *aoc_last_error(void);
+
+/**
+ * Releases a string returned by this library.  Passing NULL does nothing.
+ *
+ * # Safety
+ *
+ * `s` must be NULL or a string returned by `aoc_solve` or `aoc_last_error`
+ * that has not already been freed.
+ */
+void aoc_free_string(char *s);
+
+#endif  /* AOC_H */
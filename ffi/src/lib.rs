@@ -0,0 +1,94 @@
+//! C API over the solvers so other languages can call them in process.
+//!
+//! `include/aoc.h` declares the functions below; `test_header` checks it is
+//! current and `AOC_UPDATE_HEADER=1 cargo test -p aoc-ffi` rewrites it.
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn solve(day: u8, part: u8, input: &[u8]) -> Result<CString, String> {
+    let input = std::str::from_utf8(input).map_err(|e| format!("Input is not UTF-8: {e}"))?;
+    let answer = catch_unwind(AssertUnwindSafe(|| solutions::solve(day, part, input)))
+        .map_err(|panic| {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            format!("Solver panicked: {message}")
+        })?
+        .map_err(|e| format!("{e:#}"))?;
+    CString::new(answer).map_err(|e| format!("Answer contains a NUL byte: {e}"))
+}
+
+/// Solves `part` (1 or 2) of `day` for the UTF-8 puzzle input of `len` bytes
+/// at `input_ptr`.
+///
+/// Returns a NUL terminated answer that the caller owns and must release with
+/// `aoc_free_string`.  Returns NULL on failure, after which `aoc_last_error`
+/// explains why.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes.  It may be NULL when `len`
+/// is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    len: usize,
+) -> *mut c_char {
+    let input = if len == 0 {
+        &[][..]
+    } else if input_ptr.is_null() {
+        LAST_ERROR.with(|e| *e.borrow_mut() = Some("Input pointer is NULL".to_string()));
+        return std::ptr::null_mut();
+    } else {
+        std::slice::from_raw_parts(input_ptr, len)
+    };
+
+    match solve(day, part, input) {
+        Ok(answer) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = None);
+            answer.into_raw()
+        }
+        Err(message) => {
+            LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// The reason the last `aoc_solve` on this thread failed, or NULL if it
+/// succeeded.  The caller owns the string and must release it with
+/// `aoc_free_string`.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *mut c_char {
+    LAST_ERROR.with(|e| match &*e.borrow() {
+        Some(message) => CString::new(message.replace('\0', " "))
+            .map(CString::into_raw)
+            .unwrap_or(std::ptr::null_mut()),
+        None => std::ptr::null_mut(),
+    })
+}
+
+/// Releases a string returned by this library.  Passing NULL does nothing.
+///
+/// # Safety
+///
+/// `s` must be NULL or a string returned by `aoc_solve` or `aoc_last_error`
+/// that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
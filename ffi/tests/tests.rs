@@ -0,0 +1,67 @@
+use std::ffi::CStr;
+
+use aoc_ffi::{aoc_free_string, aoc_last_error, aoc_solve};
+
+fn take(s: *mut std::ffi::c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+    unsafe { aoc_free_string(s) };
+    Some(owned)
+}
+
+#[test]
+fn test_solve() {
+    let input = include_str!("../../day4/sample.txt");
+    let answer = unsafe { aoc_solve(4, 2, input.as_ptr(), input.len()) };
+    assert_eq!(take(answer).as_deref(), Some("4"));
+    assert_eq!(take(aoc_last_error()), None);
+}
+
+#[test]
+fn test_errors() {
+    let answer = unsafe { aoc_solve(42, 1, std::ptr::null(), 0) };
+    assert!(answer.is_null());
+    assert_eq!(take(aoc_last_error()).as_deref(), Some("No solution for day 42"));
+
+    let input = [0xffu8, 0xfe];
+    let answer = unsafe { aoc_solve(4, 1, input.as_ptr(), input.len()) };
+    assert!(answer.is_null());
+    assert!(take(aoc_last_error()).unwrap().starts_with("Input is not UTF-8"));
+
+    let answer = unsafe { aoc_solve(4, 1, std::ptr::null(), 3) };
+    assert!(answer.is_null());
+    assert_eq!(take(aoc_last_error()).as_deref(), Some("Input pointer is NULL"));
+}
+
+/// The committed header must match what cbindgen makes of `src/lib.rs`.
+#[test]
+fn test_header() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config {
+        usize_is_size_t: true,
+        ..Default::default()
+    };
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_crate(crate_dir)
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("AOC_H")
+        .with_header("/* Generated by cbindgen from ffi/src/lib.rs, do not edit. */")
+        .generate()
+        .expect("Unable to generate the C header")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let path = format!("{crate_dir}/include/aoc.h");
+    if std::env::var_os("AOC_UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+    }
+    let committed = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        committed == generated,
+        "include/aoc.h is out of date, run AOC_UPDATE_HEADER=1 cargo test -p aoc-ffi"
+    );
+}
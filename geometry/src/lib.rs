@@ -0,0 +1,451 @@
+//! 2D points, vectors and directions shared by the grid days.
+//!
+//! Coordinates follow the puzzle drawings: `x` grows to the right and `y`
+//! grows downward, so [`Dir4::Up`] is `(0, -1)`.  Grids indexed by `usize`
+//! step through [`Point::checked_add`], which refuses to walk off the top or
+//! left edge instead of wrapping.
+//!
+//! [`Point3`] and [`Vector3`] are the same idea in three dimensions, for the
+//! voxel days.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num_traits::{Signed, Zero};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy> Point<T> {
+    /// Converts to another coordinate type, failing if either coordinate
+    /// does not fit (e.g. a negative `i64` into `usize`).
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Point<U>, U::Error> {
+        Ok(Point::new(U::try_from(self.x)?, U::try_from(self.y)?))
+    }
+
+    pub fn cast<U: From<T>>(self) -> Point<U> {
+        Point::new(self.x.into(), self.y.into())
+    }
+}
+
+impl<T: Copy + PartialOrd + Zero> Point<T> {
+    /// Is the point inside a `width` x `height` grid anchored at the origin.
+    pub fn in_bounds(self, width: T, height: T) -> bool {
+        self.x >= T::zero() && self.y >= T::zero() && self.x < width && self.y < height
+    }
+}
+
+impl<T: Copy + TryInto<i64> + TryFrom<i64>> Point<T> {
+    /// Moves by `v`, or `None` if the result cannot be represented in `T`.
+    pub fn checked_add(self, v: Vector<i64>) -> Option<Self> {
+        let x: i64 = self.x.try_into().ok()?;
+        let y: i64 = self.y.try_into().ok()?;
+        Some(Point::new(
+            T::try_from(x.checked_add(v.x)?).ok()?,
+            T::try_from(y.checked_add(v.y)?).ok()?,
+        ))
+    }
+
+    /// Up, right, down and left neighbours that are representable in `T`.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |d| self.checked_add(d.vector()))
+    }
+
+    /// All eight surrounding neighbours that are representable in `T`.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |d| self.checked_add(d.vector()))
+    }
+}
+
+impl Point<usize> {
+    /// Row major index into a grid `width` wide.
+    pub fn index(self, width: usize) -> usize {
+        self.y * width + self.x
+    }
+
+    pub fn from_index(index: usize, width: usize) -> Self {
+        Point::new(index % width, index / width)
+    }
+}
+
+impl<T: Signed + Copy + Ord> Point<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (other - self).manhattan_len()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (other - self).chebyshev_len()
+    }
+}
+
+impl<T: Signed + Copy + Ord> Vector<T> {
+    pub fn manhattan_len(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev_len(self) -> T {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// A step of at most one in each axis towards the same direction.
+    pub fn signum(self) -> Self {
+        Vector::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Vector<T>) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Vector<T>) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vector<T>> for Point<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+/// The four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn vector(self) -> Vector<i64> {
+        match self {
+            Dir4::Up => Vector::new(0, -1),
+            Dir4::Right => Vector::new(1, 0),
+            Dir4::Down => Vector::new(0, 1),
+            Dir4::Left => Vector::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// The eight compass directions, clockwise from north (up).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn vector(self) -> Vector<i64> {
+        match self {
+            Dir8::N => Vector::new(0, -1),
+            Dir8::NE => Vector::new(1, -1),
+            Dir8::E => Vector::new(1, 0),
+            Dir8::SE => Vector::new(1, 1),
+            Dir8::S => Vector::new(0, 1),
+            Dir8::SW => Vector::new(-1, 1),
+            Dir8::W => Vector::new(-1, 0),
+            Dir8::NW => Vector::new(-1, -1),
+        }
+    }
+}
+impl From<Dir4> for Dir8 {
+    fn from(value: Dir4) -> Self {
+        match value {
+            Dir4::Up => Dir8::N,
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> Vector3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl Vector3<i64> {
+    /// One step across each face of a unit cube.
+    pub const FACES: [Vector3<i64>; 6] = [
+        Vector3::new(1, 0, 0),
+        Vector3::new(-1, 0, 0),
+        Vector3::new(0, 1, 0),
+        Vector3::new(0, -1, 0),
+        Vector3::new(0, 0, 1),
+        Vector3::new(0, 0, -1),
+    ];
+}
+
+impl<T: Copy + TryInto<i64> + TryFrom<i64>> Point3<T> {
+    /// Moves by `v`, or `None` if the result cannot be represented in `T`.
+    pub fn checked_add(self, v: Vector3<i64>) -> Option<Self> {
+        let x: i64 = self.x.try_into().ok()?;
+        let y: i64 = self.y.try_into().ok()?;
+        let z: i64 = self.z.try_into().ok()?;
+        Some(Point3::new(
+            T::try_from(x.checked_add(v.x)?).ok()?,
+            T::try_from(y.checked_add(v.y)?).ok()?,
+            T::try_from(z.checked_add(v.z)?).ok()?,
+        ))
+    }
+
+    /// The six face-sharing neighbours that are representable in `T`.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        Vector3::FACES
+            .into_iter()
+            .filter_map(move |v| self.checked_add(v))
+    }
+}
+
+impl<T: Signed + Copy> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Copy> Vector3<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vector3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn neg(self) -> Self::Output {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector3<T>> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+#[test]
+fn test_distances() {
+    let a = Point::new(1i64, 2);
+    let b = Point::new(-3i64, 5);
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!((b - a).signum(), Vector::new(-1, 1));
+    assert_eq!(a + (b - a), b);
+}
+
+#[test]
+fn test_checked_add() {
+    let corner = Point::new(0usize, 0);
+    assert_eq!(corner.checked_add(Dir4::Left.vector()), None);
+    assert_eq!(
+        corner.checked_add(Dir4::Down.vector()),
+        Some(Point::new(0, 1))
+    );
+    assert_eq!(corner.neighbours4().count(), 2);
+    assert_eq!(corner.neighbours8().count(), 3);
+    assert_eq!(Point::new(5i64, 5).neighbours8().count(), 8);
+}
+
+#[test]
+fn test_casts() {
+    assert_eq!(
+        Point::new(3i64, 4).try_cast::<usize>().unwrap(),
+        Point::new(3usize, 4)
+    );
+    assert!(Point::new(-1i64, 4).try_cast::<usize>().is_err());
+    assert_eq!(Point::new(3u8, 4).cast::<i64>(), Point::new(3i64, 4));
+}
+
+#[test]
+fn test_index() {
+    let p = Point::new(2usize, 3);
+    assert_eq!(p.index(5), 17);
+    assert_eq!(Point::from_index(17, 5), p);
+    assert!(p.in_bounds(5, 4));
+    assert!(!p.in_bounds(2, 4));
+}
+
+#[test]
+fn test_turns() {
+    assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+    assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+    assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+    assert_eq!(Dir8::from(Dir4::Down).vector(), Dir4::Down.vector());
+}
+
+#[test]
+fn test_point3() {
+    let p = Point3::new(1i64, 2, 3);
+    assert_eq!(p.neighbours6().count(), 6);
+    assert!(p.neighbours6().all(|n| n.manhattan(p) == 1));
+    assert_eq!(p + Vector3::new(1, 1, 1) - p, Vector3::new(1, 1, 1));
+    assert_eq!(Point3::new(0u8, 5, 5).neighbours6().count(), 5);
+    let v = Vector3::new(1i64, 2, 3);
+    assert_eq!(v.dot(Vector3::new(4, -5, 6)), 12);
+    assert_eq!(v * 2 - v, v);
+    assert_eq!(-v + v, Vector3::default());
+}
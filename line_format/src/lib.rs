@@ -0,0 +1,360 @@
+//! `#[derive(LineFormat)]` builds a `TryFrom<&str>` (and a matching `FromStr`)
+//! from a format string, replacing the hand written split-and-check parsers.
+//!
+//! ```ignore
+//! #[derive(LineFormat)]
+//! #[line_format("move {count} from {from} to {to}")]
+//! struct Command {
+//!     count: usize,
+//!     #[line_format(with = one_based)]
+//!     from: usize,
+//!     #[line_format(with = one_based)]
+//!     to: usize,
+//! }
+//! ```
+//!
+//! Literal text has to match exactly.  A `{field}` (or `{0}` for tuple fields)
+//! captures everything up to the next literal, or the rest of the line, and is
+//! parsed with `FromStr` unless the field names a `with` function taking `&str`.
+//! Enums put a format on every variant and the first one that matches wins.
+//! Use `{{` and `}}` for literal braces.
+//!
+//! The generated code reports failures as `anyhow::Error`, so the crate doing
+//! the deriving needs `anyhow` as a dependency.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, LitStr};
+
+#[proc_macro_derive(LineFormat, attributes(line_format))]
+pub fn derive_line_format(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+struct Field {
+    key: String,
+    member: syn::Member,
+    ty: syn::Type,
+    with: Option<syn::Path>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "LineFormat does not support generic types",
+        ));
+    }
+    let name = &input.ident;
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let format = format_attr(&input.attrs, input.ident.span())?;
+            let segments = parse_format(&format)?;
+            let fields = fields(&data.fields)?;
+            check_fields(&format, &segments, &fields)?;
+            let body = parse_body(&segments, &fields, &data.fields, quote!(Self));
+            quote! {
+                let __input: &str = value;
+                #body
+            }
+        }
+        Data::Enum(data) => {
+            let mut attempts = Vec::new();
+            let mut prefixes = Vec::new();
+            let mut formats = Vec::new();
+            for variant in &data.variants {
+                let format = format_attr(&variant.attrs, variant.ident.span())?;
+                let segments = parse_format(&format)?;
+                let fields = fields(&variant.fields)?;
+                check_fields(&format, &segments, &fields)?;
+                let ident = &variant.ident;
+                let body = parse_body(&segments, &fields, &variant.fields, quote!(Self::#ident));
+                attempts.push(quote! {
+                    |__input: &str| -> ::anyhow::Result<Self> { #body }
+                });
+                prefixes.push(match segments.first() {
+                    Some(Segment::Literal(l)) => l.clone(),
+                    _ => String::new(),
+                });
+                formats.push(format!("`{}`", format.value()));
+            }
+            let count = attempts.len();
+            let formats = formats.join(", ");
+            quote! {
+                let attempts: [(&str, fn(&str) -> ::anyhow::Result<Self>); #count] = [
+                    #((#prefixes, #attempts)),*
+                ];
+                let mut committed = None;
+                for (prefix, attempt) in attempts {
+                    match attempt(value) {
+                        Ok(v) => return Ok(v),
+                        // Keep the error of the first variant whose leading
+                        // literal matched, it is the one the line was aiming at.
+                        Err(e) if committed.is_none() && value.starts_with(prefix) => {
+                            committed = Some(e)
+                        }
+                        Err(_) => {}
+                    }
+                }
+                Err(committed.unwrap_or_else(|| {
+                    ::anyhow::anyhow!("`{}` did not match any of {}", value, #formats)
+                }))
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "LineFormat cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl ::core::convert::TryFrom<&str> for #name {
+            type Error = ::anyhow::Error;
+
+            fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                #body
+            }
+        }
+
+        impl ::core::str::FromStr for #name {
+            type Err = ::anyhow::Error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                <Self as ::core::convert::TryFrom<&str>>::try_from(s)
+            }
+        }
+    })
+}
+
+fn format_attr(attrs: &[syn::Attribute], span: Span) -> syn::Result<LitStr> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident("line_format"))
+        .ok_or_else(|| syn::Error::new(span, "missing #[line_format(\"...\")] attribute"))?
+        .parse_args()
+}
+
+fn fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    let mut out = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let (key, member) = match &field.ident {
+            Some(ident) => (ident.to_string(), syn::Member::Named(ident.clone())),
+            None => (index.to_string(), syn::Member::Unnamed(index.into())),
+        };
+        let mut with = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("line_format")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("with") {
+                    with = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `with = path::to::fn`"))
+                }
+            })?;
+        }
+        out.push(Field {
+            key,
+            member,
+            ty: field.ty.clone(),
+            with,
+        });
+    }
+    Ok(out)
+}
+
+fn parse_format(format: &LitStr) -> syn::Result<Vec<Segment>> {
+    let error = |msg: &str| syn::Error::new(format.span(), msg);
+    let value = format.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut key = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => key.push(c),
+                        None => return Err(error("unclosed `{` in format")),
+                    }
+                }
+                let key = key.trim().to_string();
+                if key.is_empty() {
+                    return Err(error("empty `{}` in format, name the field"));
+                }
+                if literal.is_empty() && matches!(segments.last(), Some(Segment::Field(_))) {
+                    return Err(error(
+                        "two fields need literal text between them to be told apart",
+                    ));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Field(key));
+            }
+            '}' => return Err(error("unmatched `}` in format, use `}}` for a literal brace")),
+            _ => literal.push(ch),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn check_fields(format: &LitStr, segments: &[Segment], fields: &[Field]) -> syn::Result<()> {
+    let keys: Vec<&str> = segments
+        .iter()
+        .filter_map(|s| match s {
+            Segment::Field(k) => Some(k.as_str()),
+            Segment::Literal(_) => None,
+        })
+        .collect();
+    for key in &keys {
+        if !fields.iter().any(|f| f.key == *key) {
+            return Err(syn::Error::new(
+                format.span(),
+                format!("`{{{key}}}` does not name a field"),
+            ));
+        }
+        if keys.iter().filter(|k| *k == key).count() > 1 {
+            return Err(syn::Error::new(
+                format.span(),
+                format!("`{{{key}}}` appears more than once"),
+            ));
+        }
+    }
+    for field in fields {
+        if !keys.contains(&field.key.as_str()) {
+            return Err(syn::Error::new(
+                field.member.span(),
+                format!("field `{}` is missing from the format", field.key),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Code that consumes `__input` segment by segment and evaluates to
+/// `Result<Self>` built with `ctor`.
+fn parse_body(
+    segments: &[Segment],
+    fields: &[Field],
+    shape: &Fields,
+    ctor: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let mut steps = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => steps.push(quote! {
+                rest = match rest.strip_prefix(#literal) {
+                    Some(rest) => rest,
+                    None => ::anyhow::bail!(
+                        "expected `{}` at column {} of `{}`",
+                        #literal,
+                        __input.len() - rest.len() + 1,
+                        __input
+                    ),
+                };
+            }),
+            Segment::Field(key) => {
+                let index = fields.iter().position(|f| f.key == *key).unwrap();
+                let field = &fields[index];
+                let var = format_ident!("field_{}", index);
+                let take = match segments.get(i + 1) {
+                    Some(Segment::Literal(next)) => quote! {
+                        let text = match rest.find(#next) {
+                            Some(end) => {
+                                let (text, remaining) = rest.split_at(end);
+                                rest = remaining;
+                                text
+                            }
+                            None => ::anyhow::bail!(
+                                "expected `{}` after `{}` at column {} of `{}`",
+                                #next,
+                                #key,
+                                column,
+                                __input
+                            ),
+                        };
+                    },
+                    _ => quote! {
+                        let text = rest;
+                    },
+                };
+                let ty = &field.ty;
+                let parse = match &field.with {
+                    Some(with) => quote!(#with(text)),
+                    None => quote!(<#ty as ::core::str::FromStr>::from_str(text)),
+                };
+                steps.push(quote! {
+                    let column = __input.len() - rest.len() + 1;
+                    #take
+                    let #var: #ty = #parse.map_err(|e| {
+                        ::anyhow::anyhow!(
+                            "could not parse `{}` from `{}` at column {} of `{}`: {}",
+                            #key,
+                            text,
+                            column,
+                            __input,
+                            e
+                        )
+                    })?;
+                });
+            }
+        }
+    }
+
+    let trailing = match segments.last() {
+        Some(Segment::Field(_)) => quote! {},
+        _ => quote! {
+            if !rest.is_empty() {
+                ::anyhow::bail!(
+                    "unexpected `{}` at column {} of `{}`",
+                    rest,
+                    __input.len() - rest.len() + 1,
+                    __input
+                );
+            }
+        },
+    };
+
+    let vars = (0..fields.len()).map(|i| format_ident!("field_{}", i));
+    let construct = match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!(#ctor { #(#members: #vars),* })
+        }
+        Fields::Unnamed(_) => quote!(#ctor(#(#vars),*)),
+        Fields::Unit => quote!(#ctor),
+    };
+
+    quote! {
+        #[allow(unused_mut)]
+        let mut rest: &str = __input;
+        #(#steps)*
+        #trailing
+        Ok(#construct)
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use line_format::LineFormat;
+
+#[derive(LineFormat, Debug, PartialEq)]
+#[line_format("move {count} from {from} to {to}")]
+struct Command {
+    count: usize,
+    #[line_format(with = one_based)]
+    from: usize,
+    to: usize,
+}
+
+fn one_based(s: &str) -> Result<usize> {
+    let v: usize = s.parse()?;
+    v.checked_sub(1)
+        .ok_or_else(|| anyhow::anyhow!("positions start at 1"))
+}
+
+#[derive(LineFormat, Debug, PartialEq)]
+enum Instruction {
+    #[line_format("noop")]
+    Noop,
+    #[line_format("addx {0}")]
+    Addx(i64),
+}
+
+#[derive(LineFormat, Debug, PartialEq)]
+#[line_format("{0}-{1}")]
+struct Range(u32, u32);
+
+#[derive(LineFormat, Debug, PartialEq)]
+#[line_format("{{{left}}},{right}")]
+struct Nested {
+    left: Range,
+    right: Range,
+}
+
+#[test]
+fn test_struct() -> Result<()> {
+    let c: Command = "move 3 from 2 to 9".try_into()?;
+    assert_eq!(
+        c,
+        Command {
+            count: 3,
+            from: 1,
+            to: 9
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_struct_errors() {
+    let err = Command::try_from("mvoe 3 from 2 to 9").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected `move ` at column 1 of `mvoe 3 from 2 to 9`"
+    );
+
+    let err = Command::try_from("move 3 to 9").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected ` from ` after `count` at column 6 of `move 3 to 9`"
+    );
+
+    let err = Command::try_from("move x from 2 to 9").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("could not parse `count` from `x` at column 6"));
+
+    let err = Command::try_from("move 1 from 0 to 9").unwrap_err();
+    assert!(err.to_string().ends_with("positions start at 1"));
+}
+
+#[test]
+fn test_enum() -> Result<()> {
+    assert_eq!(Instruction::try_from("noop")?, Instruction::Noop);
+    assert_eq!(Instruction::try_from("addx -4")?, Instruction::Addx(-4));
+    assert_eq!("addx 7".parse::<Instruction>()?, Instruction::Addx(7));
+    Ok(())
+}
+
+#[test]
+fn test_enum_errors() {
+    let err = Instruction::try_from("noop 1").unwrap_err();
+    assert_eq!(err.to_string(), "unexpected ` 1` at column 5 of `noop 1`");
+
+    let err = Instruction::try_from("addx five").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("could not parse `0` from `five` at column 6 of `addx five`"));
+
+    let err = Instruction::try_from("jmp 2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`jmp 2` did not match any of `noop`, `addx {0}`"
+    );
+}
+
+#[test]
+fn test_nested() -> Result<()> {
+    let n: Nested = "{2-4},6-8".try_into()?;
+    assert_eq!(
+        n,
+        Nested {
+            left: Range(2, 4),
+            right: Range(6, 8)
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! Reads puzzle inputs, which may be kept encrypted as `problem.txt.enc` so
+//! the repository can be public without redistributing them.
+//!
+//! Encrypted files are ChaCha20-Poly1305 under a key derived with Argon2 from
+//! a passphrase.  The passphrase comes from `AOC_PASSPHRASE`, else the file
+//! named by `AOC_KEY_FILE`, else `.aoc-key` at the workspace root.
+
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+const MAGIC: &[u8] = b"aoc-enc1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Reads `path`, or decrypts `path.enc` if there is no plaintext copy.  An
+/// empty `path` next to `path.enc` counts as no copy, so a placeholder left
+/// behind never hides the real input.
+pub fn read(path: impl AsRef<Path>) -> Result<String> {
+    read_using(path.as_ref(), passphrase)
+}
+
+fn read_using(path: &Path, passphrase: impl FnOnce() -> Result<Vec<u8>>) -> Result<String> {
+    let encrypted = encrypted_path(path);
+    let empty = fs::metadata(path).is_ok_and(|m| m.len() == 0);
+    if path.exists() && !(empty && encrypted.exists()) {
+        return fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()));
+    }
+    if !encrypted.exists() {
+        bail!("Could not find {} or {}", path.display(), encrypted.display());
+    }
+    let plain = decrypt(&fs::read(&encrypted)?, &passphrase()?)
+        .with_context(|| format!("Could not decrypt {}", encrypted.display()))?;
+    String::from_utf8(plain).with_context(|| format!("{} is not UTF-8", encrypted.display()))
+}
+
+/// `path` with `.enc` appended.
+pub fn encrypted_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut name = OsString::from(path.as_ref().as_os_str());
+    name.push(".enc");
+    name.into()
+}
+
+/// Replaces the plaintext `path` with `path.enc`, returning the new path.
+pub fn encrypt_file(path: impl AsRef<Path>, passphrase: &[u8]) -> Result<PathBuf> {
+    let path = path.as_ref();
+    let plain = fs::read(path).with_context(|| format!("Could not read {}", path.display()))?;
+    let encrypted = encrypted_path(path);
+    fs::write(&encrypted, encrypt(&plain, passphrase)?)
+        .with_context(|| format!("Could not write {}", encrypted.display()))?;
+    fs::remove_file(path)?;
+    Ok(encrypted)
+}
+
+/// Replaces `path.enc` with the plaintext `path`.
+pub fn decrypt_file(path: impl AsRef<Path>, passphrase: &[u8]) -> Result<()> {
+    let path = path.as_ref();
+    let encrypted = encrypted_path(path);
+    let data = fs::read(&encrypted).with_context(|| format!("Could not read {}", encrypted.display()))?;
+    let plain = decrypt(&data, passphrase)
+        .with_context(|| format!("Could not decrypt {}", encrypted.display()))?;
+    fs::write(path, plain).with_context(|| format!("Could not write {}", path.display()))?;
+    fs::remove_file(encrypted)?;
+    Ok(())
+}
+
+/// The passphrase from the environment or the key file.
+pub fn passphrase() -> Result<Vec<u8>> {
+    if let Ok(passphrase) = std::env::var("AOC_PASSPHRASE") {
+        return Ok(passphrase.into_bytes());
+    }
+    let file = std::env::var_os("AOC_KEY_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(default_key_file);
+    let mut key = fs::read(&file).with_context(|| {
+        format!(
+            "No passphrase, set AOC_PASSPHRASE or write one to {}",
+            file.display()
+        )
+    })?;
+    // Editors like to leave a newline at the end of the key file
+    while key.last().is_some_and(|b| b.is_ascii_whitespace()) {
+        key.pop();
+    }
+    if key.is_empty() {
+        bail!("The key file {} is empty", file.display());
+    }
+    Ok(key)
+}
+
+/// `.aoc-key` at the workspace root, which is ignored by git.
+pub fn default_key_file() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(".aoc-key")
+}
+
+pub fn encrypt(plain: &[u8], passphrase: &[u8]) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher(passphrase, &salt)?
+        .encrypt(&nonce, plain)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+    Ok([MAGIC, &salt, &nonce, &sealed].concat())
+}
+
+pub fn decrypt(data: &[u8], passphrase: &[u8]) -> Result<Vec<u8>> {
+    let rest = data
+        .strip_prefix(MAGIC)
+        .context("Not an encrypted puzzle input")?;
+    if rest.len() < SALT_LEN + NONCE_LEN {
+        bail!("Encrypted input is truncated");
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    cipher(passphrase, salt)?
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| anyhow!("Wrong passphrase or corrupted file"))
+}
+
+fn cipher(passphrase: &[u8], salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase, salt, &mut key)
+        .map_err(|e| anyhow!("Could not derive key: {e}"))?;
+    Ok(ChaCha20Poly1305::new(&key))
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    let sealed = encrypt(b"1000\n2000\n", b"hunter2")?;
+    assert!(sealed.starts_with(MAGIC));
+    assert_eq!(decrypt(&sealed, b"hunter2")?, b"1000\n2000\n");
+    assert!(decrypt(&sealed, b"hunter3").is_err());
+    assert!(decrypt(&sealed[..20], b"hunter2").is_err());
+    assert!(decrypt(b"1000\n2000\n", b"hunter2").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_files() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("puzzle_input_{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("problem.txt");
+    fs::write(&path, "A Y\nB X\n")?;
+
+    let encrypted = encrypt_file(&path, b"hunter2")?;
+    assert_eq!(encrypted, dir.join("problem.txt.enc"));
+    assert!(!path.exists());
+    assert!(!fs::read(&encrypted)?.starts_with(b"A Y"));
+
+    // An empty placeholder must not hide the encrypted input
+    fs::write(&path, "")?;
+    let key = || Ok(b"hunter2".to_vec());
+    assert_eq!(read_using(&path, key)?, "A Y\nB X\n");
+    fs::remove_file(&path)?;
+    assert_eq!(read_using(&path, key)?, "A Y\nB X\n");
+
+    decrypt_file(&path, b"hunter2")?;
+    assert!(!encrypted.exists());
+    assert_eq!(read(&path)?, "A Y\nB X\n");
+    // With nothing encrypted beside it an empty file is just empty input
+    fs::write(&path, "")?;
+    assert_eq!(read(&path)?, "");
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+//! Every day's solvers in one place, looked up by day and part number.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+pub type Solver = fn(&str, &Params) -> Result<String>;
+
+/// A number a solver needs beyond the puzzle input, such as a grid size that
+/// differs between the sample and the real input.
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: i64,
+}
+
+/// Values for a day's [`Param`]s, see [`Solution::params`].
+#[derive(Clone, Debug, Default)]
+pub struct Params(HashMap<&'static str, i64>);
+impl Params {
+    pub fn get(&self, name: &str) -> Result<i64> {
+        self.0
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("No parameter named {name}"))
+    }
+}
+
+pub struct Solution {
+    pub day: u8,
+    pub part1: Solver,
+    pub part2: Solver,
+    pub params: &'static [Param],
+}
+impl Solution {
+    pub fn part(&self, part: u8) -> Result<Solver> {
+        match part {
+            1 => Ok(self.part1),
+            2 => Ok(self.part2),
+            _ => anyhow::bail!("Day {} has no part {part}", self.day),
+        }
+    }
+
+    /// The day's parameters with `given` values overriding the defaults.
+    pub fn params<'a>(
+        &self,
+        given: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Params> {
+        let mut params: HashMap<_, _> = self.params.iter().map(|p| (p.name, p.default)).collect();
+        for (name, value) in given {
+            let param = self
+                .params
+                .iter()
+                .find(|p| p.name == name)
+                .ok_or_else(|| anyhow::anyhow!("Day {} has no parameter {name}", self.day))?;
+            let value = value
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Bad value {value} for {name}: {e}"))?;
+            params.insert(param.name, value);
+        }
+        Ok(Params(params))
+    }
+
+    pub fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String> {
+        (self.part(part)?)(input, params)
+    }
+}
+
+/// A day whose `part1` and `part2` only take the input.
+macro_rules! solution {
+    ($day:literal, $krate:ident) => {
+        Solution {
+            day: $day,
+            part1: |input, _| $krate::part1(input),
+            part2: |input, _| $krate::part2(input),
+            params: &[],
+        }
+    };
+}
+
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, day1),
+    solution!(2, day2),
+    solution!(3, day3),
+    solution!(4, aocday4),
+    solution!(5, aocday5),
+    solution!(6, aocday6),
+    solution!(7, aocday7),
+    solution!(8, aocday8),
+    solution!(9, aocday9),
+    solution!(10, aocday10),
+    solution!(11, aocday11),
+    solution!(12, aocday12),
+    solution!(13, aocday13),
+    solution!(14, aocday14),
+    Solution {
+        day: 15,
+        part1: |input, params| aocday15::part1_at(input, params.get("row")?),
+        part2: |input, params| aocday15::part2_within(input, params.get("bound")?),
+        params: &[
+            Param {
+                name: "row",
+                description: "The row to count positions without a beacon in",
+                default: aocday15::ROW,
+            },
+            Param {
+                name: "bound",
+                description: "The largest x and y the distress beacon can have",
+                default: aocday15::BOUND,
+            },
+        ],
+    },
+    solution!(16, aocday16),
+    solution!(17, aocday17),
+    solution!(18, aocday18),
+    solution!(19, aocday19),
+    solution!(20, aocday20),
+    solution!(21, aocday21),
+    solution!(22, aocday22),
+    solution!(23, aocday23),
+    solution!(24, aocday24),
+    solution!(25, aocday25),
+];
+
+pub fn find(day: u8) -> Result<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.day == day)
+        .ok_or_else(|| anyhow::anyhow!("No solution for day {day}"))
+}
+
+/// Solves with the day's default parameters.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String> {
+    let solution = find(day)?;
+    solution.solve(part, input, &solution.params([])?)
+}
+
+#[test]
+fn test_solve() -> Result<()> {
+    assert_eq!(solve(4, 1, include_str!("../../day4/sample.txt"))?, "2");
+    assert_eq!(solve(6, 2, "mjqjpqmgbljsphdztnvjfqwrcgsmlb")?, "19");
+
+    let day15 = find(15)?;
+    let sample = include_str!("../../day15/sample.txt");
+    assert_eq!(day15.solve(1, sample, &day15.params([("row", "10")])?)?, "26");
+    assert_eq!(day15.solve(2, sample, &day15.params([("bound", "20")])?)?, "56000011");
+    Ok(())
+}
+
+#[test]
+fn test_unknown() {
+    assert!(solve(26, 1, "").is_err());
+    assert!(solve(4, 3, "").is_err());
+    assert!(find(4).unwrap().params([("row", "10")]).is_err());
+}
+
+#[test]
+fn test_params() -> Result<()> {
+    let solution = Solution {
+        params: &[Param {
+            name: "row",
+            description: "The row to look at",
+            default: 2000000,
+        }],
+        ..solution!(4, aocday4)
+    };
+    assert_eq!(solution.params([])?.get("row")?, 2000000);
+    assert_eq!(solution.params([("row", "10")])?.get("row")?, 10);
+    assert!(solution.params([("row", "ten")]).is_err());
+    assert!(solution.params([])?.get("column").is_err());
+    Ok(())
+}
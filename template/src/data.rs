@@ -5,9 +5,9 @@ pub fn sample_data() -> Result<String> {
 }
 
 pub fn part1_problem_data() -> Result<String> {
-    Ok(include_str!("../problem.txt").to_string())
+    puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))
 }
 
 pub fn part2_problem_data() -> Result<String> {
-    Ok(include_str!("../problem.txt").to_string())
+    puzzle_input::read(concat!(env!("CARGO_MANIFEST_DIR"), "/problem.txt"))
 }